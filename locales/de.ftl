@@ -0,0 +1,116 @@
+# German
+
+## Launcher
+app-title = 7Guis - Iced
+window-title = 7Guis - Iced - { $task }
+menu-heading = 7GUIs für Iced
+menu-search = Suchen
+menu-workspace = Arbeitsbereich: mehrere Aufgaben nebeneinander
+menu-theme = Design
+menu-scale = Skalierung
+menu-text-size = Schriftgröße
+menu-language = Sprache
+nav-back = Zurück zum Menü
+banner-dismiss = Schließen
+
+theme-light = Hell
+theme-dark = Dunkel
+theme-high-contrast = Hoher Kontrast
+theme-custom = Eigenes (theme.toml)
+
+status-implemented = Umgesetzt
+status-partial = Teilweise: { $missing }
+status-not-started = Nicht begonnen
+
+## Workspace
+workspace-title = Arbeitsbereich
+workspace-empty = Leerer Bereich
+workspace-pick = Wähle eine Aufgabe, um sie hier zu öffnen
+workspace-split-right = Rechts teilen
+workspace-split-down = Unten teilen
+workspace-close = Schließen
+
+## Debugger
+debugger-resume = Fortsetzen
+debugger-position = Nachricht { $position } von { $count }
+debugger-start = (Anfang)
+
+## Tasks
+counter-title = Zähler
+counter-description = Führt benannte Zähler in Schritten, auf Wunsch zwischen Grenzen, mit einer Summe
+counter-reset = Zurücksetzen
+counter-step = Schritt
+counter-min = Min
+counter-max = Max
+counter-no-bound = keine
+counter-name = Name
+counter-total = Summe
+counter-add = Zähler hinzufügen
+counter-clear = Neu anfangen
+counter-move-up = Hoch
+counter-move-down = Runter
+counter-remove = Entfernen
+counter-file = Datei (.csv oder .json)
+counter-export = Exportieren
+counter-import = Importieren
+counter-exported = { $count } Zähler nach { $file } exportiert
+counter-imported = { $count } Zähler importiert
+counter-undo = Rückgängig
+counter-redo = Wiederholen
+counter-rate = { $rate } pro Minute
+counter-unsaved = Ein anderer Zähler ist schon geöffnet, dieser wird nicht gespeichert
+
+temperature-title = Temperaturumrechner
+temperature-description = Rechnet zwischen Temperaturskalen um, während ein Feld bearbeitet wird
+temperature-celsius = Celsius
+temperature-fahrenheit = Fahrenheit
+temperature-kelvin = Kelvin
+temperature-rankine = Rankine
+temperature-reaumur = Réaumur
+temperature-delisle = Delisle
+temperature-precision = Genauigkeit
+temperature-decimals = Nachkommastellen: { $count }
+temperature-significant = Signifikante Stellen: { $count }
+temperature-not-a-number = Keine Zahl
+temperature-below-absolute-zero = Kälter als der absolute Nullpunkt, { $limit }
+temperature-too-hot = Zu heiß, um in jede Skala umgerechnet zu werden
+temperature-other-scale = In { $symbol } angegeben, dieses Feld ist in { $field }
+temperature-missing-number = Am Ende fehlt eine Zahl
+temperature-unexpected = „{ $character }“ steht an der falschen Stelle
+temperature-unclosed-bracket = Eine Klammer wird nicht geschlossen
+temperature-division-by-zero = Division durch null
+temperature-too-deep = Zu viele Klammern oder Vorzeichen ineinander
+
+flights-title = Flugbuchung
+flights-description = Bucht einen einfachen Flug oder Hin- und Rückflug, sobald die Daten gültig sind
+flights-partial = eine Buchung wird nur auf der Konsole bestätigt
+flights-one-way = Einfacher Flug
+flights-return = Hin- und Rückflug
+flights-book = Buchen
+flights-booked = { $flight } wurde gebucht
+flights-departing = Abflug am: { $date }
+flights-returning = Rückflug am: { $date }
+
+timer-title = Timer
+timer-description = Zeigt die verstrichene Zeit im Verhältnis zu einer einstellbaren Dauer
+timer-elapsed = Verstrichene Zeit:
+timer-duration = Dauer:
+timer-reset = Zurücksetzen
+
+crud-title = CRUD
+crud-description = Erstellt, liest, ändert und löscht Namen in einer filterbaren Liste
+crud-filter-prefix = Filterpräfix:
+crud-filter = Filter
+crud-name-label = Vorname:
+crud-name = Vorname
+crud-surname-label = Nachname:
+crud-surname = Nachname
+crud-create = Erstellen
+crud-update = Ändern
+crud-delete = Löschen
+
+circles-title = Kreiszeichner
+circles-description = Zeichnet Kreise, deren Größe geändert werden kann, mit Rückgängig und Wiederholen
+
+cells-title = Zellen
+cells-description = Eine Tabellenkalkulation, deren Formeln sich mit den Zellen aktualisieren, auf die sie verweisen
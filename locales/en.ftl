@@ -0,0 +1,116 @@
+# English, also used for any message missing from another language
+
+## Launcher
+app-title = 7Guis - Iced
+window-title = 7Guis - Iced - { $task }
+menu-heading = 7GUIs for Iced
+menu-search = Search
+menu-workspace = Workspace: several tasks side by side
+menu-theme = Theme
+menu-scale = Scale
+menu-text-size = Text size
+menu-language = Language
+nav-back = Back to menu
+banner-dismiss = Dismiss
+
+theme-light = Light
+theme-dark = Dark
+theme-high-contrast = High contrast
+theme-custom = Custom (theme.toml)
+
+status-implemented = Implemented
+status-partial = Partial: { $missing }
+status-not-started = Not started
+
+## Workspace
+workspace-title = Workspace
+workspace-empty = Empty pane
+workspace-pick = Pick a task to open it here
+workspace-split-right = Split right
+workspace-split-down = Split down
+workspace-close = Close
+
+## Debugger
+debugger-resume = Resume
+debugger-position = Message { $position } of { $count }
+debugger-start = (start)
+
+## Tasks
+counter-title = Counter
+counter-description = Keeps named tallies counted by a step, within optional bounds, with a total
+counter-reset = Reset
+counter-step = Step
+counter-min = Min
+counter-max = Max
+counter-no-bound = none
+counter-name = Name
+counter-total = Total
+counter-add = Add tally
+counter-clear = Start over
+counter-move-up = Up
+counter-move-down = Down
+counter-remove = Remove
+counter-file = File (.csv or .json)
+counter-export = Export
+counter-import = Import
+counter-exported = Exported { $count } tallies to { $file }
+counter-imported = Imported { $count } tallies
+counter-undo = Undo
+counter-redo = Redo
+counter-rate = { $rate } per minute
+counter-unsaved = Another counter is open, this board isn't saved
+
+temperature-title = Temperature Converter
+temperature-description = Converts between temperature scales as any field is edited
+temperature-celsius = Celsius
+temperature-fahrenheit = Fahrenheit
+temperature-kelvin = Kelvin
+temperature-rankine = Rankine
+temperature-reaumur = Réaumur
+temperature-delisle = Delisle
+temperature-precision = Precision
+temperature-decimals = Decimals: { $count }
+temperature-significant = Significant figures: { $count }
+temperature-not-a-number = Not a number
+temperature-below-absolute-zero = Colder than absolute zero, { $limit }
+temperature-too-hot = Too hot to convert into every scale
+temperature-other-scale = Written in { $symbol }, this field is in { $field }
+temperature-missing-number = A number is missing at the end
+temperature-unexpected = “{ $character }” can't go there
+temperature-unclosed-bracket = A bracket is never closed
+temperature-division-by-zero = Division by zero
+temperature-too-deep = Too many brackets or signs inside each other
+
+flights-title = Flight Booker
+flights-description = Books a one-way or return flight once its dates are valid
+flights-partial = a booking is only confirmed on the console
+flights-one-way = One-way Flight
+flights-return = Return Flight
+flights-book = Book
+flights-booked = A { $flight } has been booked
+flights-departing = Departing on: { $date }
+flights-returning = Returning on: { $date }
+
+timer-title = Timer
+timer-description = Shows the elapsed time against an adjustable duration
+timer-elapsed = Elapsed Time:
+timer-duration = Duration:
+timer-reset = Reset
+
+crud-title = CRUD
+crud-description = Creates, reads, updates and deletes names in a filterable list
+crud-filter-prefix = Filter Prefix:
+crud-filter = Filter
+crud-name-label = Name:
+crud-name = Name
+crud-surname-label = Surname:
+crud-surname = Surname
+crud-create = Create
+crud-update = Update
+crud-delete = Delete
+
+circles-title = Circle Drawer
+circles-description = Draws circles which can be resized, with undo and redo
+
+cells-title = Cells
+cells-description = A spreadsheet whose formulas update as the cells they refer to change
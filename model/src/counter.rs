@@ -0,0 +1,623 @@
+// # Counter
+// A board of named tallies, each counted up or down by a step and optionally kept between a lower
+// and an upper bound shared by all of them. The step and bounds are kept as they were typed,
+// so a field can be invalid while it is edited. Boards can be written to and read from CSV and JSON.
+// Every change to a value is kept with the time it was made, to be undone and redone, and apart from
+// that in a longer log of activity the rate of counting and the sparkline are worked out from
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::Model;
+
+/// How many changes can be undone, older ones are forgotten
+pub const HISTORY_LIMIT: usize = 100;
+
+/// How many changes are kept for the rate and the sparkline, older ones are forgotten
+pub const ACTIVITY_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Counter {
+    tallies: Vec<Tally>,
+    // the tally the keyboard shortcuts and reset apply to, None once every tally has been removed
+    selected: Option<usize>,
+    step: String,
+    // an empty bound leaves the values unbounded on that side
+    min: String,
+    max: String,
+    // the changes which can be undone, oldest first, and those undone since, most recently undone last
+    history: VecDeque<Change>,
+    undone: Vec<Change>,
+    // every change to a value, oldest first, including undoing and redoing. Unlike the history it
+    // isn't cut back by undoing, importing or starting over
+    activity: VecDeque<Change>,
+    // the time of the latest timed message, which its changes are stamped with
+    clock: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tally {
+    pub name: String,
+    pub value: i32,
+}
+
+/// A tally's value being counted or reset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub tally: usize,
+    pub from: i32,
+    pub to: i32,
+    /// None for a change replayed from a recording made before changes had a time
+    pub at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Message {
+    // the selected tally. Recordings made before there was a decrement call it by its old name
+    #[serde(alias = "ButtonPressed")]
+    Increment,
+    Decrement,
+    Reset,
+    Select(usize),
+    // a tally by its position, which becomes the selected one
+    IncrementAt(usize),
+    DecrementAt(usize),
+    Add,
+    Rename(usize, String),
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+    // replaces every tally with those read from a file, which can't be undone
+    Import(Vec<Tally>),
+    // starts over with a single tally, keeping the step and bounds
+    Clear,
+    // puts back a board saved by an earlier run, history and all
+    Restore(Box<Counter>),
+    Undo,
+    Redo,
+    // a message sent at the given time, the changes it makes are stamped with it
+    Timed(DateTime<Utc>, Box<Message>),
+    StepUpdated(String),
+    MinUpdated(String),
+    MaxUpdated(String),
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Counter {
+            tallies: vec![Tally::default()],
+            selected: Some(0),
+            step: "1".to_string(),
+            min: String::new(),
+            max: String::new(),
+            history: VecDeque::new(),
+            undone: Vec::new(),
+            activity: VecDeque::new(),
+            clock: None,
+        }
+    }
+}
+
+impl Counter {
+    pub fn tallies(&self) -> &[Tally] {
+        &self.tallies
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// The sum of every tally, which can't overflow however many there are
+    pub fn total(&self) -> i64 {
+        self.tallies.iter().map(|tally| i64::from(tally.value)).sum()
+    }
+
+    /// The changes which can be undone, oldest first
+    pub fn history(&self) -> &VecDeque<Change> {
+        &self.history
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// How many times a tally was changed in the minute up to 'now'
+    pub fn rate(&self, index: usize, now: DateTime<Utc>) -> usize {
+        let since = now - Duration::minutes(1);
+        self.activity.iter()
+            .filter(|change| change.tally == index && change.at.is_some_and(|at| at > since && at <= now))
+            .count()
+    }
+
+    /// A tally's value over time, from just before the earliest of its timed changes still kept
+    pub fn timeline(&self, index: usize) -> Vec<(DateTime<Utc>, i32)> {
+        let mut changes = self.activity.iter()
+            .filter(|change| change.tally == index)
+            .filter_map(|change| Some((change.at?, change)))
+            .peekable();
+
+        let start = changes.peek().map(|&(at, change)| (at, change.from));
+        start.into_iter().chain(changes.map(|(at, change)| (at, change.to))).collect()
+    }
+
+    pub fn step_text(&self) -> &str {
+        &self.step
+    }
+
+    pub fn min_text(&self) -> &str {
+        &self.min
+    }
+
+    pub fn max_text(&self) -> &str {
+        &self.max
+    }
+
+    /// The step counted by, None unless it is a whole number of at least one
+    pub fn step(&self) -> Option<i32> {
+        self.step.trim().parse().ok().filter(|&step| step >= 1)
+    }
+
+    /// The lower and upper bounds, None while either isn't a whole number or they are the wrong way round
+    pub fn bounds(&self) -> Option<(Option<i32>, Option<i32>)> {
+        match (bound(&self.min)?, bound(&self.max)?) {
+            (Some(min), Some(max)) if min > max => None,
+            bounds => Some(bounds),
+        }
+    }
+
+    /// Whether the lower bound can be used, a bound above the upper bound can't
+    pub fn min_is_valid(&self) -> bool {
+        bound(&self.min).is_some() && (self.bounds().is_some() || bound(&self.max).is_none())
+    }
+
+    /// Whether the upper bound can be used, a bound below the lower bound can't
+    pub fn max_is_valid(&self) -> bool {
+        bound(&self.max).is_some() && (self.bounds().is_some() || bound(&self.min).is_none())
+    }
+
+    /// A tally's value after counting up, None when it would pass the upper bound or overflow
+    pub fn incremented(&self, index: usize) -> Option<i32> {
+        let value = self.tallies.get(index)?.value.checked_add(self.step()?)?;
+        match self.bounds()? {
+            (_, Some(max)) if value > max => None,
+            _ => Some(value),
+        }
+    }
+
+    /// A tally's value after counting down, None when it would pass the lower bound or overflow
+    pub fn decremented(&self, index: usize) -> Option<i32> {
+        let value = self.tallies.get(index)?.value.checked_sub(self.step()?)?;
+        match self.bounds()? {
+            (Some(min), _) if value < min => None,
+            _ => Some(value),
+        }
+    }
+
+    /// What a reset goes back to, zero unless the bounds leave it out
+    pub fn start(&self) -> i32 {
+        match self.bounds() {
+            Some((Some(min), _)) if min > 0 => min,
+            Some((_, Some(max))) if max < 0 => max,
+            _ => 0,
+        }
+    }
+
+    // a value outside new bounds is left as it is, only moving further out is prevented.
+    // Messages naming a tally which doesn't exist are ignored
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Increment => {
+                if let Some(index) = self.selected {
+                    self.count(index, self.incremented(index))
+                }
+            },
+            Message::Decrement => {
+                if let Some(index) = self.selected {
+                    self.count(index, self.decremented(index))
+                }
+            },
+            Message::Reset => {
+                if let Some(index) = self.selected {
+                    self.count(index, Some(self.start()))
+                }
+            },
+            Message::Select(index) => {
+                if index < self.tallies.len() {
+                    self.selected = Some(index);
+                }
+            },
+            Message::IncrementAt(index) => {
+                self.update(Message::Select(index));
+                self.count(index, self.incremented(index));
+            },
+            Message::DecrementAt(index) => {
+                self.update(Message::Select(index));
+                self.count(index, self.decremented(index));
+            },
+            Message::Add => {
+                self.tallies.push(Tally { name: String::new(), value: self.start() });
+                self.selected = Some(self.tallies.len() - 1);
+            },
+            Message::Rename(index, name) => {
+                if let Some(tally) = self.tallies.get_mut(index) {
+                    tally.name = name;
+                }
+            },
+            Message::MoveUp(index) => {
+                if index > 0 {
+                    self.swap(index - 1, index);
+                }
+            },
+            Message::MoveDown(index) => {
+                if let Some(next) = index.checked_add(1) {
+                    self.swap(index, next);
+                }
+            },
+            Message::Remove(index) => {
+                if index < self.tallies.len() {
+                    self.tallies.remove(index);
+                    self.forget(index);
+                    // the selection stays on the same tally, or the one taking the removed one's place
+                    self.selected = match self.selected {
+                        Some(selected) if selected > index => Some(selected - 1),
+                        Some(selected) if selected == self.tallies.len() => selected.checked_sub(1),
+                        selected => selected,
+                    };
+                }
+            },
+            Message::Import(tallies) => {
+                self.selected = if tallies.is_empty() { None } else { Some(0) };
+                self.replace(tallies);
+            },
+            Message::Clear => {
+                self.replace(vec![Tally { name: String::new(), value: self.start() }]);
+                self.selected = Some(0);
+            },
+            Message::Restore(board) => *self = *board,
+            // the tally a change is undone or redone on becomes the selected one
+            Message::Undo => {
+                if let Some(change) = self.history.pop_back() {
+                    self.tallies[change.tally].value = change.from;
+                    self.selected = Some(change.tally);
+                    self.log(change.tally, change.to, change.from);
+                    self.undone.push(change);
+                }
+            },
+            Message::Redo => {
+                if let Some(change) = self.undone.pop() {
+                    self.tallies[change.tally].value = change.to;
+                    self.selected = Some(change.tally);
+                    self.log(change.tally, change.from, change.to);
+                    self.history.push_back(change);
+                }
+            },
+            Message::Timed(at, message) => {
+                self.clock = Some(at);
+                self.update(*message);
+            },
+            Message::StepUpdated(s) => self.step = s,
+            Message::MinUpdated(s) => self.min = s,
+            Message::MaxUpdated(s) => self.max = s,
+        }
+    }
+
+    // a change which leaves the value as it was isn't kept. A new change can't be followed by a redo
+    fn count(&mut self, index: usize, value: Option<i32>) {
+        let from = match self.tallies.get(index) {
+            Some(tally) => tally.value,
+            None => return,
+        };
+
+        match value {
+            Some(to) if to != from => {
+                self.tallies[index].value = to;
+                self.history.push_back(Change { tally: index, from, to, at: self.clock });
+                if self.history.len() > HISTORY_LIMIT {
+                    self.history.pop_front();
+                }
+                self.undone.clear();
+                self.log(index, from, to);
+            },
+            _ => (),
+        }
+    }
+
+    fn log(&mut self, tally: usize, from: i32, to: i32) {
+        self.activity.push_back(Change { tally, from, to, at: self.clock });
+        if self.activity.len() > ACTIVITY_LIMIT {
+            self.activity.pop_front();
+        }
+    }
+
+    // replaces every tally, which can't be undone. The activity of a tally left in the same place
+    // goes on, with the change to its new value
+    fn replace(&mut self, tallies: Vec<Tally>) {
+        let old = std::mem::replace(&mut self.tallies, tallies);
+        let count = self.tallies.len();
+        self.activity.retain(|change| change.tally < count);
+
+        let changed: Vec<(usize, i32, i32)> = old.iter().zip(&self.tallies).enumerate()
+            .filter(|(_, (old, new))| old.value != new.value)
+            .map(|(index, (old, new))| (index, old.value, new.value))
+            .collect();
+        for (index, from, to) in changed {
+            self.log(index, from, to);
+        }
+        self.history.clear();
+        self.undone.clear();
+    }
+
+    // the changes to a removed tally are dropped, those to the tallies after it follow them down
+    fn forget(&mut self, index: usize) {
+        self.history.retain(|change| change.tally != index);
+        self.undone.retain(|change| change.tally != index);
+        self.activity.retain(|change| change.tally != index);
+        for change in self.history.iter_mut().chain(self.undone.iter_mut()).chain(self.activity.iter_mut()) {
+            if change.tally > index {
+                change.tally -= 1;
+            }
+        }
+    }
+
+    // the selection and the changes move with the tally they are on
+    fn swap(&mut self, a: usize, b: usize) {
+        if b < self.tallies.len() {
+            self.tallies.swap(a, b);
+            for change in self.history.iter_mut().chain(self.undone.iter_mut()).chain(self.activity.iter_mut()) {
+                change.tally = match change.tally {
+                    tally if tally == a => b,
+                    tally if tally == b => a,
+                    tally => tally,
+                };
+            }
+            self.selected = match self.selected {
+                Some(selected) if selected == a => Some(b),
+                Some(selected) if selected == b => Some(a),
+                selected => selected,
+            };
+        }
+    }
+
+    /// Reads a board saved by serializing it, whatever refers to a tally which isn't there is dropped
+    pub fn from_state(json: &str) -> Result<Counter, String> {
+        let mut counter: Counter = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let count = counter.tallies.len();
+        counter.selected = counter.selected.filter(|&index| index < count).or((count > 0).then_some(0));
+        counter.history.retain(|change| change.tally < count);
+        counter.undone.retain(|change| change.tally < count);
+        counter.activity.retain(|change| change.tally < count);
+        while counter.history.len() > HISTORY_LIMIT {
+            counter.history.pop_front();
+        }
+        while counter.activity.len() > ACTIVITY_LIMIT {
+            counter.activity.pop_front();
+        }
+        Ok(counter)
+    }
+
+    /// The tallies as CSV with a header row, names are quoted where they need to be
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("name,value\n");
+        for tally in &self.tallies {
+            csv.push_str(&format!("{},{}\n", csv_field(&tally.name), tally.value));
+        }
+        csv
+    }
+
+    /// The tallies as a JSON array of objects with a name and a value
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.tallies).expect("tallies are plain data")
+    }
+}
+
+impl Model for Counter {
+    type Message = Message;
+    const NAME: &'static str = "counter";
+
+    fn update(&mut self, message: Message) {
+        Counter::update(self, message)
+    }
+}
+
+// a bound as typed, None when it isn't a whole number. An empty bound is no bound at all
+fn bound(s: &str) -> Option<Option<i32>> {
+    match s.trim() {
+        "" => Some(None),
+        s => s.parse().ok().map(Some),
+    }
+}
+
+
+// ## Reading tallies back
+
+/// Reads tallies written by 'to_csv', the header row may be left out
+pub fn from_csv(csv: &str) -> Result<Vec<Tally>, String> {
+    let mut tallies = Vec::new();
+
+    for (number, record) in csv_records(csv)?.into_iter().enumerate() {
+        let line = number + 1;
+        match record.as_slice() {
+            [name, value] if line == 1 && name.eq_ignore_ascii_case("name") && value.eq_ignore_ascii_case("value") => (),
+            [name, value] => {
+                let value = value.trim().parse()
+                    .map_err(|_| format!("record {}: expected a whole number, found '{}'", line, value))?;
+                tallies.push(Tally { name: name.clone(), value });
+            },
+            [] => (),
+            fields => return Err(format!("record {}: expected a name and a value, found {} fields", line, fields.len())),
+        }
+    }
+
+    Ok(tallies)
+}
+
+/// Reads tallies written by 'to_json'
+pub fn from_json(json: &str) -> Result<Vec<Tally>, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+// splits CSV into records of fields, a quoted field can hold commas, quotes and line breaks
+fn csv_records(csv: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            },
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => (),
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            },
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err("a quoted field is never closed".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // blank lines have a single empty field
+    Ok(records.into_iter().map(|record| match record.as_slice() {
+        [field] if field.is_empty() => Vec::new(),
+        _ => record,
+    }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(name: &str, value: i32) -> Tally {
+        Tally { name: name.to_string(), value }
+    }
+
+    fn board(names: &[&str]) -> Counter {
+        let mut counter = Counter::default();
+        counter.update(Message::Import(names.iter().map(|name| tally(name, 0)).collect()));
+        counter
+    }
+
+    fn names(counter: &Counter) -> Vec<&str> {
+        counter.tallies().iter().map(|tally| tally.name.as_str()).collect()
+    }
+
+    #[test]
+    fn csv_round_trips_commas_quotes_and_line_breaks() {
+        let tallies = vec![
+            tally("plain", 1),
+            tally("a, b", -5),
+            tally("say \"hi\"", 0),
+            tally("two\nlines", i32::MAX),
+            tally("", i32::MIN),
+        ];
+        let mut counter = Counter::default();
+        counter.update(Message::Import(tallies.clone()));
+
+        assert_eq!(from_csv(&counter.to_csv()), Ok(tallies.clone()));
+        assert_eq!(from_json(&counter.to_json()), Ok(tallies));
+    }
+
+    #[test]
+    fn csv_header_is_optional() {
+        let tallies = vec![tally("a", 1), tally("b", 2)];
+
+        assert_eq!(from_csv("name,value\na,1\nb,2\n"), Ok(tallies.clone()));
+        assert_eq!(from_csv("Name,Value\r\na,1\r\nb,2"), Ok(tallies.clone()));
+        assert_eq!(from_csv("a,1\n\nb, 2\n"), Ok(tallies));
+    }
+
+    #[test]
+    fn csv_errors_name_the_record() {
+        assert_eq!(from_csv("name,value\na,1\nb,x\n"), Err("record 3: expected a whole number, found 'x'".to_string()));
+        assert_eq!(from_csv("a,1,2\n"), Err("record 1: expected a name and a value, found 3 fields".to_string()));
+        assert_eq!(from_csv("\"a,1\n"), Err("a quoted field is never closed".to_string()));
+    }
+
+    #[test]
+    fn rate_isnt_bounded_by_undo() {
+        let start = Utc::now();
+        let mut counter = board(&["a"]);
+        for second in 0..150 {
+            let at = start + Duration::milliseconds(second * 100);
+            counter.update(Message::Timed(at, Box::new(Message::Increment)));
+        }
+        let now = start + Duration::seconds(20);
+        assert_eq!(counter.rate(0, now), 150);
+        assert_eq!(counter.history().len(), HISTORY_LIMIT);
+
+        counter.update(Message::Timed(now, Box::new(Message::Undo)));
+        assert_eq!(counter.rate(0, now), 151);
+        assert_eq!(counter.timeline(0).last(), Some(&(now, 149)));
+
+        counter.update(Message::Timed(now, Box::new(Message::Clear)));
+        assert_eq!(counter.rate(0, now), 152);
+        assert_eq!(counter.rate(0, now + Duration::minutes(2)), 0);
+    }
+
+    #[test]
+    fn moving_the_last_tally_down_does_nothing() {
+        let mut counter = board(&["a", "b"]);
+        counter.update(Message::MoveDown(1));
+        counter.update(Message::MoveDown(usize::MAX));
+
+        assert_eq!(names(&counter), ["a", "b"]);
+    }
+
+    #[test]
+    fn undo_follows_a_tally_moved_up() {
+        let mut counter = board(&["a", "b"]);
+        counter.update(Message::IncrementAt(1));
+        counter.update(Message::MoveUp(1));
+        assert_eq!(names(&counter), ["b", "a"]);
+        assert_eq!(counter.selected(), Some(0));
+
+        counter.update(Message::Undo);
+        assert_eq!(counter.tallies(), [tally("b", 0), tally("a", 0)]);
+        assert_eq!(counter.selected(), Some(0));
+
+        counter.update(Message::Redo);
+        assert_eq!(counter.tallies(), [tally("b", 1), tally("a", 0)]);
+    }
+
+    #[test]
+    fn undo_skips_a_removed_tally() {
+        let mut counter = board(&["a", "b", "c"]);
+        counter.update(Message::IncrementAt(0));
+        counter.update(Message::IncrementAt(2));
+        counter.update(Message::Remove(0));
+        assert_eq!(counter.tallies(), [tally("b", 0), tally("c", 1)]);
+
+        counter.update(Message::Undo);
+        assert_eq!(counter.tallies(), [tally("b", 0), tally("c", 0)]);
+        assert_eq!(counter.selected(), Some(1));
+        // the change to the removed tally went with it
+        assert!(!counter.can_undo());
+    }
+}
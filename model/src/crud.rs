@@ -0,0 +1,129 @@
+// # CRUD
+// A filterable list of names which can be created, updated and deleted
+
+use serde::{Deserialize, Serialize};
+use crate::Model;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct List {
+    items: Vec<String>,
+    selected: Option<usize>,
+}
+
+impl Default for List {
+    fn default() -> List {
+        let v = [
+            "Emil, Hans",
+            "Mustermann, Max",
+            "Tisch, Roman",
+        ];
+
+        List {
+            items: v.iter().map(|s| s.to_string()).collect(),
+            selected: None,
+        }
+    }
+}
+
+impl List {
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// The items containing the filter, ignoring case, with their index in the list
+    pub fn filtered<'a>(&'a self, filter: &str) -> impl Iterator<Item = (usize, &'a str)> {
+        let filter = filter.to_lowercase();
+
+        self.items
+            .iter()
+            .enumerate()
+            .filter(move |(_, item)| item.to_lowercase().contains(&filter))
+            .map(|(i, item)| (i, item.as_str()))
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.items.len() {
+            self.selected = Some(index);
+        }
+    }
+
+    pub fn push(&mut self, label: &str) {
+        self.items.push(label.to_string());
+    }
+
+    pub fn remove(&mut self) {
+        if let Some(i) = self.selected.take() {
+            self.items.remove(i);
+        }
+    }
+
+    pub fn replace(&mut self, label: &str) {
+        if let Some(i) = self.selected {
+            self.items[i] = label.to_string();
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    CreatePressed,
+    UpdatePressed,
+    DeletePressed,
+    FilterUpdated(String),
+    NameUpdated(String),
+    SurnameUpdated(String),
+    SelectionChanged(usize),
+}
+
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Crud {
+    names: List,
+    filter: String,
+    name: String,
+    surname: String,
+}
+
+impl Crud {
+    pub fn names(&self) -> &List {
+        &self.names
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn surname(&self) -> &str {
+        &self.surname
+    }
+
+    fn make_name(&self) -> String {
+        format!("{}, {}", self.surname, self.name)
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::FilterUpdated(s) => self.filter = s,
+            Message::NameUpdated(s) => self.name = s,
+            Message::SurnameUpdated(s) => self.surname = s,
+            Message::SelectionChanged(i) => self.names.select(i),
+            Message::CreatePressed => self.names.push(&self.make_name()),
+            Message::UpdatePressed => self.names.replace(&self.make_name()),
+            Message::DeletePressed => self.names.remove(),
+        }
+    }
+}
+
+impl Model for Crud {
+    type Message = Message;
+    const NAME: &'static str = "crud";
+
+    fn update(&mut self, message: Message) {
+        Crud::update(self, message)
+    }
+}
@@ -0,0 +1,210 @@
+// # Flight Booker
+// Validates a one-way or return flight's dates before it can be booked
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::Model;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Flights {
+    type_selected: FlightType,
+    date_format: DateFormat,
+    outbound: String,
+    inbound: String,
+    booking: Option<Booking>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    TypeSelected(FlightType),
+    OutboundUpdated(String),
+    InboundUpdated(String),
+    DateFormatSelected(DateFormat),
+    BookRequest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FlightType {
+    #[default]
+    OneWay,
+    Return,
+}
+
+impl FlightType {
+    pub const ALL: [FlightType; 2] = [
+        FlightType::OneWay,
+        FlightType::Return,
+    ];
+}
+
+impl std::fmt::Display for FlightType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FlightType::OneWay => "One-way Flight",
+                FlightType::Return => "Return Flight",
+            }
+        )
+    }
+}
+
+/// The order dates are entered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DateFormat {
+    #[default]
+    DayMonthYear,
+    YearMonthDay,
+    MonthDayYear,
+    DayMonthYearDots,
+}
+
+impl DateFormat {
+    pub const ALL: [DateFormat; 4] = [
+        DateFormat::DayMonthYear,
+        DateFormat::YearMonthDay,
+        DateFormat::MonthDayYear,
+        DateFormat::DayMonthYearDots,
+    ];
+
+    /// The chrono format string
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            DateFormat::DayMonthYear => "%d-%m-%Y",
+            DateFormat::YearMonthDay => "%Y-%m-%d",
+            DateFormat::MonthDayYear => "%m/%d/%Y",
+            DateFormat::DayMonthYearDots => "%d.%m.%Y",
+        }
+    }
+
+    /// A readable name, also used when the format is saved
+    pub fn name(&self) -> &'static str {
+        match self {
+            DateFormat::DayMonthYear => "dd-mm-yyyy",
+            DateFormat::YearMonthDay => "yyyy-mm-dd",
+            DateFormat::MonthDayYear => "mm/dd/yyyy",
+            DateFormat::DayMonthYearDots => "dd.mm.yyyy",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DateFormat> {
+        DateFormat::ALL.iter().copied().find(|format| format.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// The state of a date field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Valid,
+    Invalid,
+    // the field does not apply to the selected flight type
+    Disabled,
+}
+
+/// A flight which passed validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Booking {
+    pub flight_type: FlightType,
+    pub outbound: NaiveDate,
+    pub inbound: Option<NaiveDate>,
+}
+
+impl Flights {
+    pub fn type_selected(&self) -> FlightType {
+        self.type_selected
+    }
+
+    pub fn date_format(&self) -> DateFormat {
+        self.date_format
+    }
+
+    pub fn outbound(&self) -> &str {
+        &self.outbound
+    }
+
+    pub fn inbound(&self) -> &str {
+        &self.inbound
+    }
+
+    /// The most recent successful booking
+    pub fn booking(&self) -> Option<&Booking> {
+        self.booking.as_ref()
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::TypeSelected(t) => self.type_selected = t,
+            Message::OutboundUpdated(s) => self.outbound = s,
+            Message::InboundUpdated(s) => {
+                // the return date can't be edited for a one-way flight
+                if self.type_selected == FlightType::Return {
+                    self.inbound = s;
+                }
+            },
+            Message::DateFormatSelected(format) => self.date_format = format,
+            Message::BookRequest => {
+                if let Some(booking) = self.validate() {
+                    self.booking = Some(booking);
+                }
+            },
+        }
+    }
+
+    pub fn outbound_field(&self) -> Field {
+        match self.parse(&self.outbound) {
+            Some(_) => Field::Valid,
+            None => Field::Invalid,
+        }
+    }
+
+    pub fn inbound_field(&self) -> Field {
+        if self.type_selected == FlightType::OneWay {
+            return Field::Disabled;
+        }
+
+        match (self.parse(&self.inbound), self.parse(&self.outbound)) {
+            // the date will parse but may not be valid as it may be before the outbound date,
+            // at least one day has to pass before the return flight
+            (Some(indate), Some(outdate)) if indate.signed_duration_since(outdate).num_days() <= 0 => {
+                Field::Invalid
+            },
+            (Some(_), _) => Field::Valid,
+            (None, _) => Field::Invalid,
+        }
+    }
+
+    /// Whether the current dates can be booked
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_some()
+    }
+
+    fn validate(&self) -> Option<Booking> {
+        let outbound = self.parse(&self.outbound)?;
+
+        let inbound = match self.inbound_field() {
+            Field::Valid => Some(self.parse(&self.inbound)?),
+            Field::Disabled => None,
+            Field::Invalid => return None,
+        };
+
+        Some(Booking {
+            flight_type: self.type_selected,
+            outbound,
+            inbound,
+        })
+    }
+
+    fn parse(&self, s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, self.date_format.pattern()).ok()
+    }
+}
+
+impl Model for Flights {
+    type Message = Message;
+    const NAME: &'static str = "flights";
+
+    fn update(&mut self, message: Message) {
+        Flights::update(self, message)
+    }
+}
@@ -0,0 +1,26 @@
+//! The state and state transitions of each of the 7GUIs tasks.
+//!
+//! Every model is driven Elm-style by passing its `Message` to `update`,
+//! nothing here depends on a GUI toolkit so the models can be tested and
+//! reused by any front-end.
+
+pub mod counter;
+pub mod temperature;
+pub mod flights;
+pub mod timer;
+pub mod crud;
+pub mod replay;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// The interface shared by every model, used to record and replay sessions without a GUI
+pub trait Model: Default + Clone + Serialize + 'static {
+    type Message: Debug + Clone + Send + Sync + Serialize + DeserializeOwned + 'static;
+
+    /// The name of the task the model belongs to
+    const NAME: &'static str;
+
+    fn update(&mut self, message: Self::Message);
+}
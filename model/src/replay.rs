@@ -0,0 +1,103 @@
+// # Replay
+// A recording is a JSON Lines file with one 'Record' per message, in the order they were handled.
+// Feeding it back into fresh models reproduces the session exactly, as every change to a model
+// goes through its messages. The timestamps are only there to read, they don't affect the result
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::io::BufRead;
+
+use crate::{counter, crud, flights, temperature, timer, Model};
+
+/// A message handled by one of the tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Record {
+    /// Milliseconds since the recording started
+    pub at_ms: u64,
+    pub task: String,
+    /// Which copy of the task handled the message when there are several, such as "pane-2" for one
+    /// in a workspace pane. None for the launcher's own, and in recordings made before panes were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub message: Value,
+}
+
+impl Record {
+    /// The task's name, followed by the copy of it after a '/' when it isn't the launcher's own
+    pub fn key(&self) -> String {
+        match &self.instance {
+            Some(instance) => format!("{}/{}", self.task, instance),
+            None => self.task.clone(),
+        }
+    }
+}
+
+/// Replays a recording from the default state of each model, returning the final state of every
+/// task named in it. Each copy of a task is replayed into a model of its own, keyed as 'Record::key'
+pub fn replay(recording: impl BufRead) -> Result<Map<String, Value>, String> {
+    let mut models: Vec<(String, Box<dyn Replayed>)> = Vec::new();
+
+    for (number, line) in recording.lines().enumerate() {
+        let line = line.map_err(|e| format!("the recording could not be read: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let at = |error: String| format!("line {}: {}", number + 1, error);
+        let record: Record = serde_json::from_str(&line).map_err(|e| at(e.to_string()))?;
+
+        let key = record.key();
+        let index = match models.iter().position(|(name, _)| *name == key) {
+            Some(index) => index,
+            None => {
+                let model = create(&record.task).ok_or_else(|| at(format!("unknown task '{}'", record.task)))?;
+                models.push((key, model));
+                models.len() - 1
+            }
+        };
+
+        models[index].1.apply(record.message).map_err(at)?;
+    }
+
+    models.iter()
+        .map(|(name, model)| Ok((name.clone(), model.state()?)))
+        .collect()
+}
+
+// The models a recording can refer to
+const MODELS: [(&str, Create); 5] = [
+    (counter::Counter::NAME, new::<counter::Counter>),
+    (temperature::Temperature::NAME, new::<temperature::Temperature>),
+    (flights::Flights::NAME, new::<flights::Flights>),
+    (timer::Timer::NAME, new::<timer::Timer>),
+    (crud::Crud::NAME, new::<crud::Crud>),
+];
+
+type Create = fn() -> Box<dyn Replayed>;
+
+fn new<M: Model + 'static>() -> Box<dyn Replayed> {
+    Box::new(M::default())
+}
+
+fn create(task: &str) -> Option<Box<dyn Replayed>> {
+    MODELS.iter().find(|(name, _)| *name == task).map(|(_, create)| create())
+}
+
+// Lets models with different message types be replayed side by side
+trait Replayed {
+    fn apply(&mut self, message: Value) -> Result<(), String>;
+    fn state(&self) -> Result<Value, String>;
+}
+
+impl<M: Model> Replayed for M {
+    fn apply(&mut self, message: Value) -> Result<(), String> {
+        let message = serde_json::from_value(message)
+            .map_err(|e| format!("not a {} message: {}", M::NAME, e))?;
+        self.update(message);
+        Ok(())
+    }
+
+    fn state(&self) -> Result<Value, String> {
+        serde_json::to_value(self).map_err(|e| format!("the {} state could not be written: {}", M::NAME, e))
+    }
+}
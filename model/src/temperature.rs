@@ -0,0 +1,457 @@
+// # Temperature Converter
+// A text field for each temperature scale kept in sync, editing one converts the value into all the others.
+// The temperature is held once, in Kelvin, and every scale is a linear function of it. Only the field
+// being typed in keeps its text, the others are written from the Kelvin value at the chosen precision,
+// so converting back and forth never loses anything to rounding.
+// A field which isn't a temperature is flagged and leaves the others as they were.
+// A field can also hold arithmetic, such as (212 - 32) / 2, which is worked out as it is typed
+
+use serde::{Deserialize, Serialize};
+use crate::Model;
+
+mod expression;
+pub use expression::Problem;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Temperature {
+    // None until a valid temperature has been typed
+    kelvin: Option<f64>,
+    // the field last typed in and its text as it was typed, None until one has been
+    editing: Option<(Scale, String)>,
+    // only the field being typed in can be invalid, the others show the last valid temperature
+    invalid: Option<Invalid>,
+    precision: Precision,
+}
+
+/// How much of a temperature is written out, in decimal places or significant figures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Precision {
+    Decimals(u8),
+    Significant(u8),
+}
+
+impl Precision {
+    /// The most decimal places or significant figures which can be chosen
+    pub const MAX: u8 = 6;
+
+    /// Every precision which can be chosen, the decimal places first
+    pub fn all() -> Vec<Precision> {
+        let decimals = (0..=Precision::MAX).map(Precision::Decimals);
+        let significant = (1..=Precision::MAX).map(Precision::Significant);
+        decimals.chain(significant).collect()
+    }
+
+    /// The precision unless it asks for more digits than can be chosen, or for no significant figures
+    pub fn checked(self) -> Option<Precision> {
+        match self {
+            Precision::Decimals(decimals) if decimals <= Precision::MAX => Some(self),
+            Precision::Significant(figures) if (1..=Precision::MAX).contains(&figures) => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Precision::Decimals(1)
+    }
+}
+
+/// Why the text of a field isn't a temperature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Invalid {
+    NotANumber,
+    BelowAbsoluteZero,
+    // too large to be written in one of the scales
+    TooHot,
+    // the text ends in the symbol of another scale
+    OtherScale(Scale),
+    // arithmetic which can't be worked out
+    Expression(Problem),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    Updated(Scale, String),
+    PrecisionSelected(Precision),
+    // the fields of recordings made when there were only Celsius and Fahrenheit
+    CUpdated(String),
+    FUpdated(String),
+}
+
+/// A temperature scale, converted to and from Kelvin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+    Reaumur,
+    Delisle,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 6] = [Scale::Celsius, Scale::Fahrenheit, Scale::Kelvin, Scale::Rankine,
+                                 Scale::Reaumur, Scale::Delisle];
+
+    /// The name of the scale in lower case, as it is saved
+    pub fn name(self) -> &'static str {
+        match self {
+            Scale::Celsius => "celsius",
+            Scale::Fahrenheit => "fahrenheit",
+            Scale::Kelvin => "kelvin",
+            Scale::Rankine => "rankine",
+            Scale::Reaumur => "reaumur",
+            Scale::Delisle => "delisle",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Scale> {
+        Scale::ALL.iter().copied().find(|scale| scale.name().eq_ignore_ascii_case(name))
+    }
+
+    // the symbols the scale is recognised by after a number, in lower case, the usual one first
+    fn symbols(self) -> &'static [&'static str] {
+        match self {
+            Scale::Celsius => &["°c", "c"],
+            Scale::Fahrenheit => &["°f", "f"],
+            Scale::Kelvin => &["k", "°k"],
+            Scale::Rankine => &["°r", "r", "°ra", "ra"],
+            Scale::Reaumur => &["°ré", "ré", "°re", "re"],
+            Scale::Delisle => &["°de", "de"],
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Scale::Celsius => "°C",
+            Scale::Fahrenheit => "°F",
+            Scale::Kelvin => "K",
+            Scale::Rankine => "°R",
+            Scale::Reaumur => "°Ré",
+            Scale::Delisle => "°De",
+        }
+    }
+
+    // a temperature in the scale is 'value * factor + offset' in Kelvin
+    fn factor_offset(self) -> (f64, f64) {
+        match self {
+            Scale::Celsius => (1., 273.15),
+            Scale::Fahrenheit => (5. / 9., 459.67 * 5. / 9.),
+            Scale::Kelvin => (1., 0.),
+            Scale::Rankine => (5. / 9., 0.),
+            Scale::Reaumur => (5. / 4., 273.15),
+            // Delisle counts down from the boiling point of water
+            Scale::Delisle => (-2. / 3., 373.15),
+        }
+    }
+
+    pub fn to_kelvin(self, value: f64) -> f64 {
+        let (factor, offset) = self.factor_offset();
+        value * factor + offset
+    }
+
+    pub fn from_kelvin(self, kelvin: f64) -> f64 {
+        let (factor, offset) = self.factor_offset();
+        (kelvin - offset) / factor
+    }
+}
+
+impl Temperature {
+    /// The text of the field being typed in as it was typed, None for the other fields
+    pub fn typed(&self, scale: Scale) -> Option<&str> {
+        match &self.editing {
+            Some((editing, text)) if *editing == scale => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The text of a scale's field, as it was typed for the field being typed in
+    pub fn text(&self, scale: Scale) -> String {
+        match (&self.editing, self.kelvin) {
+            (Some((editing, text)), _) if *editing == scale => text.clone(),
+            (_, Some(kelvin)) => format(scale.from_kelvin(kelvin), self.precision),
+            (_, None) => String::new(),
+        }
+    }
+
+    /// Why a field's text isn't a temperature, None when it is one or is empty
+    pub fn invalid(&self, scale: Scale) -> Option<Invalid> {
+        match &self.editing {
+            Some((editing, _)) if *editing == scale => self.invalid,
+            _ => None,
+        }
+    }
+
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// The temperature last typed correctly, in Kelvin
+    pub fn kelvin(&self) -> Option<f64> {
+        self.kelvin
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            // an empty field is left to be typed in, it isn't an error
+            // a field left invalid goes back to the last valid temperature once another is typed in
+            Message::Updated(scale, s) => {
+                self.invalid = None;
+                match kelvin(scale, &s) {
+                    Ok(Some(kelvin)) => self.kelvin = Some(kelvin),
+                    Ok(None) => (),
+                    Err(reason) => self.invalid = Some(reason),
+                }
+                self.editing = Some((scale, s));
+            },
+            Message::PrecisionSelected(precision) => {
+                if let Some(precision) = precision.checked() {
+                    self.precision = precision;
+                }
+            },
+            Message::CUpdated(s) => self.update(Message::Updated(Scale::Celsius, s)),
+            Message::FUpdated(s) => self.update(Message::Updated(Scale::Fahrenheit, s)),
+        }
+    }
+}
+
+/// A value written out at a precision, rounding half away from zero. It is first rounded to the
+/// 15 significant figures an f64 holds exactly, so 373.15 isn't written 373.1 for being stored as 373.1499…
+/// An infinite value or NaN, which has no digits to round, is written as Rust writes it
+pub fn format(value: f64, precision: Precision) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let scientific = format!("{:.14e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').expect("scientific notation has an exponent");
+    let exponent: i32 = exponent.parse().expect("the exponent is a whole number");
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+
+    // the places after the point to round to, negative to round to tens, hundreds and so on
+    let decimals = match precision {
+        Precision::Decimals(decimals) => i32::from(decimals),
+        Precision::Significant(figures) => i32::from(figures) - 1 - exponent,
+    };
+
+    // the digits count in units of 10^(exponent - 14), they are rounded to units of 10^-decimals
+    let rounded = match exponent - 14 + decimals {
+        shift if shift >= 0 => digits + &"0".repeat(shift as usize),
+        shift if shift < -15 => String::new(),
+        shift => {
+            let units: u64 = digits.parse().expect("15 digits fit in a u64");
+            let unit = 10u64.pow(shift.unsigned_abs());
+            ((units + unit / 2) / unit).to_string()
+        },
+    };
+    let rounded = rounded.trim_start_matches('0');
+    // a carry into a new leading digit, as 99.95 rounding to 100.0, leaves a figure too many
+    let (rounded, decimals) = match precision {
+        Precision::Significant(figures) if rounded.len() > usize::from(figures) => (&rounded[..rounded.len() - 1], decimals - 1),
+        _ => (rounded, decimals),
+    };
+
+    let text = match decimals {
+        decimals if decimals > 0 => {
+            let decimals = decimals as usize;
+            let padded = format!("{:0>width$}", rounded, width = decimals + 1);
+            let (whole, fraction) = padded.split_at(padded.len() - decimals);
+            format!("{}.{}", whole, fraction)
+        },
+        _ if rounded.is_empty() => "0".to_string(),
+        _ => rounded.to_string() + &"0".repeat(decimals.unsigned_abs() as usize),
+    };
+
+    // a value rounded to zero has no sign
+    match value.is_sign_negative() && text.contains(|c: char| c.is_ascii_digit() && c != '0') {
+        true => format!("-{}", text),
+        false => text,
+    }
+}
+
+// the temperature a field's text is, in Kelvin. Anything within rounding of absolute zero is taken to be it.
+// A temperature too hot to be written in every scale is refused, 1e308 K is beyond an f64 in Fahrenheit
+fn kelvin(scale: Scale, s: &str) -> Result<Option<f64>, Invalid> {
+    if s.trim().is_empty() {
+        return Ok(None);
+    }
+
+    match scale.to_kelvin(parse(scale, s)?) {
+        kelvin if !kelvin.is_finite() || Scale::ALL.iter().any(|scale| !scale.from_kelvin(kelvin).is_finite()) => {
+            Err(Invalid::TooHot)
+        },
+        kelvin if kelvin < -1e-9 => Err(Invalid::BelowAbsoluteZero),
+        kelvin => Ok(Some(kelvin.max(0.))),
+    }
+}
+
+/// Reads a number or arithmetic typed in a scale's field, leniently. Numbers can have digits grouped by
+/// commas, spaces or apostrophes and a decimal comma where a comma can't be grouping, and the scale's
+/// symbol can follow the whole
+pub fn parse(scale: Scale, text: &str) -> Result<f64, Invalid> {
+    let text = text.trim().to_lowercase();
+
+    // the longest symbol the text ends with is the one it was written with, "°c" rather than "c".
+    // A symbol only follows a number or a bracket or percentage ending one, the 'f' of "inf" isn't one
+    let after_number = |symbol: &str| {
+        text.strip_suffix(symbol).is_some_and(|number| {
+            number.ends_with(|c: char| c.is_ascii_digit() || c.is_whitespace() || matches!(c, '.' | ',' | ')' | '%'))
+        })
+    };
+    let symbol = Scale::ALL.iter()
+        .flat_map(|&scale| scale.symbols().iter().map(move |&symbol| (scale, symbol)).chain(Some((scale, "°"))))
+        .filter(|(_, symbol)| after_number(symbol))
+        .max_by_key(|(_, symbol)| symbol.len());
+    let number = match symbol {
+        Some((other, symbol)) if other != scale && symbol != "°" => return Err(Invalid::OtherScale(other)),
+        Some((_, symbol)) => text[..text.len() - symbol.len()].trim_end(),
+        None => &text,
+    };
+
+    expression::evaluate(number).map_err(|problem| match problem {
+        Problem::NotANumber => Invalid::NotANumber,
+        problem => Invalid::Expression(problem),
+    })
+}
+
+// a number without a sign. Its digits may be grouped by spaces, apostrophes or commas, and a lone
+// comma is a decimal point
+fn number(text: &str) -> Option<f64> {
+    let number: String = text.chars().filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{202f}' | '\'' | '’')).collect();
+    // commas only group the digits before the point, "1.000,5" isn't a thousand and a half
+    let (whole, fraction) = match number.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (number.as_str(), None),
+    };
+    if fraction.is_some_and(|fraction| fraction.contains(',')) {
+        return None;
+    }
+    let number = match whole.matches(',').count() {
+        0 => number.clone(),
+        _ if grouped(whole) => number.replace(',', ""),
+        1 if fraction.is_none() => number.replace(',', "."),
+        _ => return None,
+    };
+
+    // the digits are checked first, Rust would also read "inf" and "nan"
+    if !number.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+// whether the digits before the point are grouped by commas in threes, as in 1,000,000
+fn grouped(whole: &str) -> bool {
+    let mut groups = whole.split(',');
+    let first = groups.next().unwrap_or_default();
+    let digits = |group: &str| group.chars().all(|c| c.is_ascii_digit());
+
+    (1..=3).contains(&first.len()) && digits(first) && groups.all(|group| group.len() == 3 && digits(group))
+}
+
+impl Model for Temperature {
+    type Message = Message;
+    const NAME: &'static str = "temperature";
+
+    fn update(&mut self, message: Message) {
+        Temperature::update(self, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_rounds_half_away_from_zero() {
+        assert_eq!(format(2.5, Precision::Decimals(0)), "3");
+        assert_eq!(format(-2.5, Precision::Decimals(0)), "-3");
+        assert_eq!(format(373.15, Precision::Decimals(1)), "373.2");
+        assert_eq!(format(-273.15, Precision::Decimals(1)), "-273.2");
+    }
+
+    #[test]
+    fn format_carries_into_the_next_digit() {
+        assert_eq!(format(9.96, Precision::Decimals(1)), "10.0");
+        assert_eq!(format(99.95, Precision::Significant(3)), "100");
+        assert_eq!(format(0.999_96, Precision::Decimals(4)), "1.0000");
+    }
+
+    #[test]
+    fn format_to_significant_figures() {
+        assert_eq!(format(1234.5, Precision::Significant(2)), "1200");
+        assert_eq!(format(0.000_123_456, Precision::Significant(3)), "0.000123");
+        assert_eq!(format(0., Precision::Decimals(2)), "0.00");
+    }
+
+    #[test]
+    fn format_drops_the_sign_of_a_value_rounded_to_zero() {
+        assert_eq!(format(-0.04, Precision::Decimals(1)), "0.0");
+        assert_eq!(format(-0.0, Precision::Significant(1)), "0");
+    }
+
+    #[test]
+    fn format_writes_values_without_digits() {
+        assert_eq!(format(f64::INFINITY, Precision::Decimals(1)), "inf");
+        assert_eq!(format(f64::NEG_INFINITY, Precision::Significant(2)), "-inf");
+        assert_eq!(format(f64::NAN, Precision::Decimals(0)), "NaN");
+    }
+
+    #[test]
+    fn parse_is_lenient() {
+        assert_eq!(parse(Scale::Celsius, "36,6"), Ok(36.6));
+        assert_eq!(parse(Scale::Celsius, "1 000"), Ok(1000.));
+        assert_eq!(parse(Scale::Celsius, "1,234.5"), Ok(1234.5));
+        assert_eq!(parse(Scale::Celsius, "1,234"), Ok(1234.));
+        assert_eq!(parse(Scale::Celsius, "-1'234,5"), Ok(-1234.5));
+        assert_eq!(parse(Scale::Celsius, "+5"), Ok(5.));
+        assert_eq!(parse(Scale::Celsius, "− 3"), Ok(-3.));
+        assert_eq!(parse(Scale::Celsius, "21 °C"), Ok(21.));
+        assert_eq!(parse(Scale::Celsius, "21°"), Ok(21.));
+    }
+
+    #[test]
+    fn parse_refuses_another_scale() {
+        assert_eq!(parse(Scale::Celsius, "70 °F"), Err(Invalid::OtherScale(Scale::Fahrenheit)));
+        assert_eq!(parse(Scale::Fahrenheit, "70 °F"), Ok(70.));
+    }
+
+    #[test]
+    fn parse_refuses_what_isnt_a_number() {
+        for text in ["abc", "inf", "nan", "1.2.3", "1,5.3", "1,23,4"] {
+            assert_eq!(parse(Scale::Celsius, text), Err(Invalid::NotANumber), "{}", text);
+        }
+    }
+
+    #[test]
+    fn parse_refuses_commas_after_the_point() {
+        assert_eq!(parse(Scale::Celsius, "1.000,5"), Err(Invalid::NotANumber));
+        assert_eq!(parse(Scale::Celsius, "1.5,000"), Err(Invalid::NotANumber));
+    }
+
+    #[test]
+    fn fields_are_written_from_kelvin() {
+        let mut temperature = Temperature::default();
+        temperature.update(Message::Updated(Scale::Celsius, "100".to_string()));
+
+        assert_eq!(temperature.text(Scale::Celsius), "100");
+        assert_eq!(temperature.text(Scale::Fahrenheit), "212.0");
+        assert_eq!(temperature.text(Scale::Kelvin), "373.2");
+    }
+
+    #[test]
+    fn too_hot_for_another_scale_is_refused() {
+        for (scale, text) in [(Scale::Kelvin, "1e308"), (Scale::Reaumur, "1.5e308")] {
+            let mut temperature = Temperature::default();
+            temperature.update(Message::Updated(scale, text.to_string()));
+
+            assert_eq!(temperature.invalid(scale), Some(Invalid::TooHot));
+            assert_eq!(temperature.kelvin(), None);
+            // the other fields are still written, rather than panicking
+            for scale in Scale::ALL.iter().copied() {
+                temperature.text(scale);
+            }
+        }
+    }
+}
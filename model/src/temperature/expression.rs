@@ -0,0 +1,269 @@
+// # Expressions
+// Arithmetic typed into a temperature field: + - * / with the usual precedence, brackets, a sign
+// before any operand and percentages. A percentage added or taken away is a share of the value
+// before it, so 20 + 5% is 21, otherwise it is a fraction, so 20 * 5% is 1
+
+use serde::Serialize;
+
+/// Why an expression can't be worked out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Problem {
+    // an operand which isn't a number, or a result too large to be one
+    NotANumber,
+    // the expression ends where a number should follow
+    MissingNumber,
+    // a character where it can't be, such as a closing bracket with nothing open
+    Unexpected(char),
+    UnclosedBracket,
+    DivisionByZero,
+    // more brackets or signs inside each other than are worked out
+    TooDeep,
+}
+
+// how many brackets and signs can be inside each other, each takes a few calls of the parser
+// so without a limit a long enough run of them would overflow the stack
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(f64),
+    Plus,
+    Minus,
+    Times,
+    Divide,
+    Percent,
+    Open,
+    Close,
+}
+
+/// The value of an expression, its numbers are read as leniently as a field holding only a number
+pub fn evaluate(text: &str) -> Result<f64, Problem> {
+    let mut parser = Parser { tokens: tokens(text)?, next: 0, depth: 0 };
+    let value = parser.expression()?;
+
+    match parser.tokens.get(parser.next) {
+        Some(&(_, c)) => Err(Problem::Unexpected(c)),
+        None if value.is_finite() => Ok(value),
+        None => Err(Problem::NotANumber),
+    }
+}
+
+fn is_operator(c: char) -> bool {
+    matches!(c, '+' | '-' | '−' | '*' | '×' | '/' | '÷' | '%' | '(' | ')')
+}
+
+// each token with the character it starts with, to point out one which is out of place
+fn tokens(text: &str) -> Result<Vec<(Token, char)>, Problem> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while let Some(&c) = chars.get(index) {
+        let token = match c {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            },
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' => Token::Times,
+            '/' | '÷' => Token::Divide,
+            '%' => Token::Percent,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            // a number runs up to the next operator, spaces within it group its digits
+            _ => {
+                let start = index;
+                while chars.get(index).is_some_and(|&c| !is_operator(c)) {
+                    // the sign of an exponent belongs to the number, as in 1e-3
+                    if chars[index] == 'e' && index > start && matches!(chars.get(index + 1), Some('+' | '-')) {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+                let number: String = chars[start..index].iter().collect();
+                tokens.push((Token::Number(super::number(number.trim()).ok_or(Problem::NotANumber)?), c));
+                continue;
+            },
+        };
+        tokens.push((token, c));
+        index += 1;
+    }
+
+    Ok(tokens)
+}
+
+// ## Recursive descent
+// expression = term (('+' | '-') term)*
+// term       = unary (('*' | '/') unary)*
+// unary      = ('+' | '-') unary | primary '%'?
+// primary    = number | '(' expression ')'
+struct Parser {
+    tokens: Vec<(Token, char)>,
+    next: usize,
+    // how many brackets and signs the parser is inside
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.next).map(|&(token, _)| token)
+    }
+
+    // works out a rule one level deeper, past 'MAX_DEPTH' the expression is given up on
+    fn nested<T>(&mut self, rule: fn(&mut Self) -> Result<T, Problem>) -> Result<T, Problem> {
+        if self.depth == MAX_DEPTH {
+            return Err(Problem::TooDeep);
+        }
+        self.depth += 1;
+        let result = rule(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn expression(&mut self) -> Result<f64, Problem> {
+        let (mut value, _) = self.term()?;
+        loop {
+            let sign = match self.peek() {
+                Some(Token::Plus) => 1.,
+                Some(Token::Minus) => -1.,
+                _ => return Ok(value),
+            };
+            self.next += 1;
+
+            let (operand, percentage) = self.term()?;
+            value += sign * if percentage { value * operand } else { operand };
+        }
+    }
+
+    // the value, and whether it is nothing but a percentage
+    fn term(&mut self) -> Result<(f64, bool), Problem> {
+        let (mut value, mut percentage) = self.unary()?;
+        loop {
+            let times = match self.peek() {
+                Some(Token::Times) => true,
+                Some(Token::Divide) => false,
+                _ => return Ok((value, percentage)),
+            };
+            self.next += 1;
+
+            let (operand, _) = self.unary()?;
+            percentage = false;
+            value = match times {
+                true => value * operand,
+                false if operand == 0. => return Err(Problem::DivisionByZero),
+                false => value / operand,
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<(f64, bool), Problem> {
+        match self.peek() {
+            Some(Token::Plus) => {
+                self.next += 1;
+                self.nested(Self::unary)
+            },
+            Some(Token::Minus) => {
+                self.next += 1;
+                self.nested(Self::unary).map(|(value, percentage)| (-value, percentage))
+            },
+            _ => {
+                let value = self.primary()?;
+                match self.peek() {
+                    Some(Token::Percent) => {
+                        self.next += 1;
+                        Ok((value / 100., true))
+                    },
+                    _ => Ok((value, false)),
+                }
+            },
+        }
+    }
+
+    fn primary(&mut self) -> Result<f64, Problem> {
+        let (token, c) = self.tokens.get(self.next).copied().ok_or(Problem::MissingNumber)?;
+        self.next += 1;
+
+        match token {
+            Token::Number(value) => Ok(value),
+            Token::Open => {
+                let value = self.nested(Self::expression)?;
+                match self.tokens.get(self.next) {
+                    Some((Token::Close, _)) => {
+                        self.next += 1;
+                        Ok(value)
+                    },
+                    Some(&(_, c)) => Err(Problem::Unexpected(c)),
+                    None => Err(Problem::UnclosedBracket),
+                }
+            },
+            _ => Err(Problem::Unexpected(c)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temperature::{parse, Invalid, Scale};
+
+    #[test]
+    fn arithmetic_follows_precedence_and_brackets() {
+        assert_eq!(evaluate("98.6 - 1.5"), Ok(97.1));
+        assert_eq!(evaluate("(212-32)/2"), Ok(90.));
+        assert_eq!(evaluate("2 + 3 * 4"), Ok(14.));
+        assert_eq!(evaluate("(2 + 3) * 4"), Ok(20.));
+        assert_eq!(evaluate("10 - 4 - 3"), Ok(3.));
+        assert_eq!(evaluate("10 × 2 ÷ 4"), Ok(5.));
+    }
+
+    #[test]
+    fn signs_go_before_any_operand() {
+        assert_eq!(evaluate("-(2 + 3)"), Ok(-5.));
+        assert_eq!(evaluate("--5"), Ok(5.));
+        assert_eq!(evaluate("3 * -2"), Ok(-6.));
+        assert_eq!(evaluate("1e-3 + 1"), Ok(1.001));
+    }
+
+    #[test]
+    fn percentages_added_are_a_share_of_the_value_before() {
+        assert_eq!(evaluate("20 + 5%"), Ok(21.));
+        assert_eq!(evaluate("20 - 5%"), Ok(19.));
+        assert_eq!(evaluate("20 * 5%"), Ok(1.));
+        assert_eq!(evaluate("5%"), Ok(0.05));
+    }
+
+    #[test]
+    fn numbers_are_read_leniently() {
+        assert_eq!(evaluate("1 000 + 1"), Ok(1001.));
+        assert_eq!(evaluate("1,5 + 1,5"), Ok(3.));
+    }
+
+    #[test]
+    fn problems_are_reported() {
+        assert_eq!(evaluate("1/0"), Err(Problem::DivisionByZero));
+        assert_eq!(evaluate("1/(2 - 2)"), Err(Problem::DivisionByZero));
+        assert_eq!(evaluate("(1 + 2"), Err(Problem::UnclosedBracket));
+        assert_eq!(evaluate("1 + 2)"), Err(Problem::Unexpected(')')));
+        assert_eq!(evaluate("2 (3)"), Err(Problem::Unexpected('(')));
+        assert_eq!(evaluate("1 +"), Err(Problem::MissingNumber));
+        assert_eq!(evaluate("abc + 1"), Err(Problem::NotANumber));
+        assert_eq!(evaluate("1e308 * 10"), Err(Problem::NotANumber));
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth| "(".repeat(depth) + "1" + &")".repeat(depth);
+        assert_eq!(evaluate(&nested(MAX_DEPTH)), Ok(1.));
+        assert_eq!(evaluate(&nested(MAX_DEPTH + 1)), Err(Problem::TooDeep));
+        assert_eq!(evaluate(&("(".repeat(100_000) + "1")), Err(Problem::TooDeep));
+        assert_eq!(evaluate(&("-".repeat(100_000) + "1")), Err(Problem::TooDeep));
+    }
+
+    #[test]
+    fn a_symbol_can_follow_the_whole() {
+        assert_eq!(parse(Scale::Celsius, "(20 + 5) °C"), Ok(25.));
+        assert_eq!(parse(Scale::Celsius, "20 + 5%°c"), Ok(21.));
+        assert_eq!(parse(Scale::Celsius, "(1 + 2"), Err(Invalid::Expression(Problem::UnclosedBracket)));
+    }
+}
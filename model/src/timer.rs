@@ -0,0 +1,66 @@
+// # Timer
+// Elapsed time which accumulates until it reaches an adjustable duration
+
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use crate::Model;
+
+/// The durations which can be selected, in seconds
+pub const DURATION_RANGE: RangeInclusive<f32> = 1.0..=60.;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Timer {
+    elapsed: f32,
+    max_time: f32,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Timer {
+            elapsed: 0.,
+            max_time: 30.,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    ResetPressed,
+    SliderChange(f32),
+    // the number of seconds since the previous tick
+    Tic(f32),
+}
+
+impl Timer {
+    /// Seconds elapsed since the timer was reset
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Seconds the timer runs for
+    pub fn max_time(&self) -> f32 {
+        self.max_time
+    }
+
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::ResetPressed => self.elapsed = 0.,
+            Message::SliderChange(v) => self.max_time = v,
+            Message::Tic(seconds) => {
+                // once the duration has been reached the timer stops, raising the duration resumes it
+                if self.elapsed < self.max_time {
+                    self.elapsed = (self.elapsed + seconds).min(self.max_time);
+                }
+            },
+        }
+    }
+}
+
+impl Model for Timer {
+    type Message = Message;
+    const NAME: &'static str = "timer";
+
+    fn update(&mut self, message: Message) {
+        Timer::update(self, message)
+    }
+}
@@ -0,0 +1,135 @@
+// # Command line interface
+// Selects a task to open directly and overrides its window settings
+
+use std::path::PathBuf;
+use crate::task::{self, Entry};
+use crate::locale::Language;
+use crate::scale::{Scale, TextSize};
+use crate::theme::Preset;
+
+pub const USAGE: &str = "\
+Usage: Iced-7guis [TASK] [OPTIONS]
+
+Opens the launcher menu, or TASK directly when one is given.
+
+Options:
+    --width <PIXELS>     Overrides the width of the window
+    --height <PIXELS>    Overrides the height of the window
+    --resizable          Allows the window to be resized
+    --fixed              Prevents the window from being resized
+    --theme <THEME>      Selects the theme: light, dark, high-contrast or custom
+    --scale <PERCENT>    Scales the whole interface, from 75 to 300
+    --text-size <PIXELS> Sets the size of body text, from 10 to 40
+    --language <CODE>    Selects the language: en or de
+    --fresh              Starts the counter from an empty board rather than the saved one
+    --record <FILE>      Records every message handled by the tasks to FILE
+    --replay <FILE>      Replays a recording without opening a window and prints the final state
+    --list               Lists the available tasks
+    -h, --help           Prints this message";
+
+// What the program has been asked to do
+pub enum Command {
+    Help,
+    List,
+    Replay(PathBuf),
+    Launch(Launch),
+}
+
+// Options for opening a window, anything left as None keeps the task's default
+#[derive(Default)]
+pub struct Launch {
+    pub task: Option<&'static Entry>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub resizable: Option<bool>,
+    pub theme: Option<Preset>,
+    pub scale: Option<Scale>,
+    pub text_size: Option<TextSize>,
+    pub language: Option<Language>,
+    pub fresh: bool,
+    pub record: Option<PathBuf>,
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut launch = Launch::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "--replay" => return Ok(Command::Replay(file(&arg, args.next())?)),
+            "--width" => launch.width = Some(pixels(&arg, args.next())?),
+            "--height" => launch.height = Some(pixels(&arg, args.next())?),
+            "--resizable" => launch.resizable = Some(true),
+            "--fixed" => launch.resizable = Some(false),
+            "--theme" => launch.theme = Some(theme(&arg, args.next())?),
+            "--scale" => {
+                let percent = number(&arg, args.next())?;
+                launch.scale = Some(Scale::new(percent).ok_or_else(|| {
+                    format!("{} expects a percentage from {} to {}, found {}", arg, Scale::MIN, Scale::MAX, percent)
+                })?);
+            },
+            "--text-size" => {
+                let pixels = number(&arg, args.next())?;
+                launch.text_size = Some(TextSize::new(pixels).ok_or_else(|| {
+                    format!("{} expects a size from {} to {} pixels, found {}", arg, TextSize::MIN, TextSize::MAX, pixels)
+                })?);
+            },
+            "--language" => launch.language = Some(language(&arg, args.next())?),
+            "--fresh" => launch.fresh = true,
+            "--record" => launch.record = Some(file(&arg, args.next())?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+            _ if launch.task.is_some() => return Err(format!("unexpected argument '{}'", arg)),
+            _ => {
+                let task = task::find(&arg).ok_or_else(|| {
+                    format!("unknown task '{}', use --list to see the available tasks", arg)
+                })?;
+                launch.task = Some(task);
+            }
+        }
+    }
+
+    Ok(Command::Launch(launch))
+}
+
+// parses the value following a size option
+fn pixels(option: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", option))?;
+
+    match value.parse::<u32>() {
+        Ok(pixels) if pixels > 0 => Ok(pixels),
+        _ => Err(format!("{} expects a positive number of pixels, found '{}'", option, value)),
+    }
+}
+
+// parses the value following an option taking a whole number, its range is checked by the caller
+fn number(option: &str, value: Option<String>) -> Result<u16, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", option))?;
+
+    value.parse().map_err(|_| format!("{} expects a whole number, found '{}'", option, value))
+}
+
+// parses the value following an option naming a file
+fn file(option: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value.map(PathBuf::from).ok_or_else(|| format!("{} requires a file", option))
+}
+
+// parses the value following the theme option
+fn theme(option: &str, value: Option<String>) -> Result<Preset, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", option))?;
+
+    Preset::from_name(&value).ok_or_else(|| {
+        let names: Vec<&str> = Preset::ALL.iter().map(|preset| preset.name()).collect();
+        format!("unknown theme '{}', expected one of: {}", value, names.join(", "))
+    })
+}
+
+// parses the value following the language option
+fn language(option: &str, value: Option<String>) -> Result<Language, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", option))?;
+
+    Language::from_code(&value).ok_or_else(|| {
+        let codes: Vec<&str> = Language::ALL.iter().map(|language| language.code()).collect();
+        format!("unknown language '{}', expected one of: {}", value, codes.join(", "))
+    })
+}
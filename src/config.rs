@@ -0,0 +1,41 @@
+// # Configuration files
+// Files the user can edit live in a directory of their own under the platform's config directory,
+// e.g. ~/.config/iced-7guis on Linux. State the application keeps for itself goes under the data
+// directory instead, e.g. ~/.local/share/iced-7guis
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const DIR_NAME: &str = "iced-7guis";
+
+// The location of a configuration file, None when the platform has no config directory
+pub fn path(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(DIR_NAME).join(file_name))
+}
+
+// The location of a file of saved state, None when the platform has no data directory
+pub fn data_path(file_name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DIR_NAME).join(file_name))
+}
+
+// Replaces a file's contents all at once: the text is written to a file alongside and then renamed over it,
+// so a crash part way through leaves the previous contents rather than half of the new ones
+pub fn write_atomic(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{} could not be created: {}", dir.display(), e))?;
+    }
+
+    let partial = path.with_extension("partial");
+    let written = File::create(&partial)
+        .and_then(|mut file| {
+            file.write_all(text.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&partial, path));
+
+    written.map_err(|e| {
+        let _ = fs::remove_file(&partial);
+        format!("{} could not be written: {}", path.display(), e)
+    })
+}
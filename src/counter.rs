@@ -1,73 +1,604 @@
-use iced::{button, Align, Button, Row, Element, Sandbox, Settings, Text, HorizontalAlignment, Length};
+use iced::{button, scrollable, text_input, time, window, Align, Button, Canvas, Color, Column, Row, Element, Point,
+           Rectangle, Scrollable, Text, TextInput, HorizontalAlignment, Length, Size, Subscription};
+use iced::canvas::{self, Cursor, Frame, Geometry, Path as Line, Stroke};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use sevenguis_model::counter::{self, Tally};
+use crate::config;
+use crate::locale::{tr, tr_with};
+use crate::task::{GuiTask, Preferences, Status};
+use crate::theme::{self, Input, Theme};
 
 // the window size is an unsigned 32 bit integer, the padding is unsigned 16 bit integer
-const SIZE: (u32, u32) = (250, 80);
-const PAD: u16 = 25;
+const DEFAULT_SIZE: (u32, u32) = (560, 500);
+const MIN_SIZE: (u32, u32) = (300, 500);
+const PAD: u16 = 15;
+const SPACING: u16 = 10;
+// below this width a tally's name goes above its buttons, and the step and bounds are stacked
+const NARROW: f32 = 480.;
+const PAD_NARROW: u16 = 10;
+// wide enough for a six figure tally at the default text size
+const VALUE_WIDTH: u16 = 80;
+const SPARKLINE_HEIGHT: u16 = 30;
+// how often the rate and sparkline catch up with the time, and the precision changes are stamped with
+const TIC: Duration = Duration::from_millis(250);
+// where the board is exported to until another file is chosen
+const FILE_NAME: &str = "tallies.csv";
+// the board as it was left, in the data directory
+const STATE_FILE: &str = "counter.json";
 
-pub fn main() -> iced::Result{
-    // Set the window properties
-    let mut settings = Settings::default();
-    settings.window.size = SIZE;
-    settings.window.resizable = false;
+// set by --fresh, counters opened in this run then start from an empty board,
+// which replaces the saved one at its first change
+static FRESH: AtomicBool = AtomicBool::new(false);
 
-    // run the application
-    Counter::run(settings)
+pub fn start_fresh() {
+    FRESH.store(true, Ordering::Relaxed);
 }
 
+// taken by the counter which keeps the saved board, so counters in the launcher and the workspace's
+// panes don't overwrite each other's. It is given back when that counter is closed
+static KEEPS_BOARD: AtomicBool = AtomicBool::new(false);
 
-#[derive(Default)]
-struct Counter {
-    // counter value
-    value: i32,
-    btn: button::State,
+
+pub struct Counter {
+    model: counter::Counter,
+    rows: Vec<TallyRow>,
+    scroll: scrollable::State,
+    add: button::State,
+    reset: button::State,
+    clear: button::State,
+    undo: button::State,
+    redo: button::State,
+    // the time of the latest tic, changes are stamped with it so they are the same when recorded and when handled
+    now: DateTime<Utc>,
+    step: text_input::State,
+    min: text_input::State,
+    max: text_input::State,
+    // the file exported to and imported from, CSV or JSON by its extension
+    file: String,
+    file_input: text_input::State,
+    export: button::State,
+    import: button::State,
+    // what came of the last export or import
+    outcome: Option<Result<String, String>>,
+    // whether this is the counter whose board is saved, the others start empty and aren't saved
+    keeps_board: bool,
 }
 
-// define the possible interactions of the application
-#[derive(Debug, Clone, Copy)]
-enum Message {
-    ButtonPressed,
+#[derive(Debug, Clone)]
+pub enum Message {
+    Model(counter::Message),
+    FileUpdated(String),
+    Export,
+    Import,
+    // the file to import or the saved board couldn't be read, a successful import is prepared
+    // into the model's 'Import' and the saved board is restored with the model's 'Restore'
+    ReadFailed(String),
+    Tic(DateTime<Utc>),
 }
 
-impl Sandbox for Counter {
+impl GuiTask for Counter {
     type Message = Message;
+    type Model = counter::Counter;
 
+    const STATUS: Status = Status::Implemented;
+    const THUMBNAIL: &'static [u8] = include_bytes!("../images/Counter.PNG");
+
+    fn window() -> window::Settings {
+        window::Settings {
+            size: DEFAULT_SIZE,
+            min_size: Some(MIN_SIZE),
+            ..window::Settings::default()
+        }
+    }
+
+    // only the first counter open keeps the saved board, which it picks up in 'restore'.
+    // Any other opened alongside it starts empty and says it isn't saved
     fn new() -> Self {
-        Self::default()
+        let dir = dirs::document_dir().or_else(dirs::home_dir).unwrap_or_default();
+        let keeps_board = KEEPS_BOARD.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_ok();
+        let outcome = match keeps_board {
+            true => None,
+            false => Some(Ok(tr("counter-unsaved"))),
+        };
+
+        Counter {
+            model: counter::Counter::default(),
+            rows: Vec::new(),
+            scroll: scrollable::State::default(),
+            add: button::State::default(),
+            reset: button::State::default(),
+            clear: button::State::default(),
+            undo: button::State::default(),
+            redo: button::State::default(),
+            now: Utc::now(),
+            step: text_input::State::default(),
+            min: text_input::State::default(),
+            max: text_input::State::default(),
+            file: dir.join(FILE_NAME).display().to_string(),
+            file_input: text_input::State::default(),
+            export: button::State::default(),
+            import: button::State::default(),
+            outcome,
+            keeps_board,
+        }
     }
 
-    fn title(&self) -> String {
-        String::from("Counter")
+    // the file is read once, the tallies in it are recorded and applied rather than where they came from
+    fn prepare(&self, message: Message) -> Message {
+        match message {
+            Message::Import => match self.read() {
+                Ok(tallies) => Message::Model(counter::Message::Import(tallies)),
+                Err(error) => Message::ReadFailed(error),
+            },
+            message => message,
+        }
+    }
+
+    // whatever changes a value is timed, so the history and activity replay with the times they were recorded at
+    fn model_message(&self, message: &Message) -> Option<counter::Message> {
+        match message {
+            Message::Model(message @ (counter::Message::Increment | counter::Message::Decrement
+                | counter::Message::Reset | counter::Message::IncrementAt(_) | counter::Message::DecrementAt(_)
+                | counter::Message::Undo | counter::Message::Redo | counter::Message::Import(_) | counter::Message::Clear)) => {
+                Some(counter::Message::Timed(self.now, Box::new(message.clone())))
+            },
+            Message::Model(message) => Some(message.clone()),
+            Message::FileUpdated(_) | Message::Export | Message::Import | Message::ReadFailed(_) | Message::Tic(_) => None,
+        }
+    }
+
+    fn model(&self) -> &Self::Model {
+        &self.model
+    }
+
+    fn model_mut(&mut self) -> &mut Self::Model {
+        &mut self.model
+    }
+
+    // the saved board follows the debugger, so resuming from an earlier state sticks
+    fn rewind(&mut self, model: Self::Model) {
+        self.model = model;
+        self.save();
     }
 
-    // linking the enum to the logic
     fn update(&mut self, message:Message) {
         match message {
-            Message::ButtonPressed  => {
-                self.value +=1;
+            Message::Model(ref model) => {
+                if let counter::Message::Import(tallies) = model {
+                    self.outcome = Some(Ok(tr_with("counter-imported", &[("count", &tallies.len())])));
+                }
+                if let Some(message) = self.model_message(&message) {
+                    self.model.update(message);
+                    self.save();
+                }
+            },
+            Message::FileUpdated(file) => self.file = file,
+            Message::Export => self.outcome = Some(self.write()),
+            // only reached when the message wasn't prepared first
+            Message::Import => self.update(self.prepare(Message::Import)),
+            Message::ReadFailed(error) => self.outcome = Some(Err(error)),
+            Message::Tic(now) => self.now = now,
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        time::every(TIC).map(|_| Message::Tic(Utc::now()))
+    }
+
+    // + and - count the selected tally, 0 resets it, unless a field is being typed in.
+    // Ctrl+Z undoes and Ctrl+Y or Ctrl+Shift+Z redoes
+    fn shortcuts(&self) -> Subscription<Message> {
+        iced_native::subscription::events_with(shortcut)
+    }
+
+    // the board is picked up where it was left, through a message so it is recorded and the debugger can
+    // go back to before it. An unreadable board is reported and an empty one used instead.
+    // The step and bounds saved in the preferences are applied over the board's
+    fn restore(&self, preferences: &Preferences) -> Vec<Message> {
+        let board = match self.keeps_board {
+            true => load().transpose(),
+            false => None,
+        };
+        let board = board.map(|board| match board {
+            Ok(board) => Message::Model(counter::Message::Restore(Box::new(board))),
+            Err(error) => Message::ReadFailed(error),
+        });
+
+        let fields = [
+            ("step", counter::Message::StepUpdated as fn(String) -> counter::Message),
+            ("min", counter::Message::MinUpdated),
+            ("max", counter::Message::MaxUpdated),
+        ];
+
+        let mut messages: Vec<Message> = board.into_iter().chain(fields.iter()
+            .filter_map(|(key, message)| {
+                let number = preferences.get(*key)?.as_integer()?;
+                Some(Message::Model(message(number.to_string())))
+            }))
+            .collect();
+        if let Some(file) = preferences.get("file").and_then(|file| file.as_str()) {
+            messages.push(Message::FileUpdated(file.to_string()));
+        }
+        messages
+    }
+
+    // only a usable step and bounds are saved
+    fn preferences(&self) -> Preferences {
+        let mut preferences = Preferences::new();
+        if let Some(step) = self.model.step() {
+            preferences.insert("step".to_string(), i64::from(step).into());
+        }
+        if let Some((min, max)) = self.model.bounds() {
+            if let Some(min) = min {
+                preferences.insert("min".to_string(), i64::from(min).into());
+            }
+            if let Some(max) = max {
+                preferences.insert("max".to_string(), i64::from(max).into());
             }
         }
+        preferences.insert("file".to_string(), self.file.clone().into());
+        preferences
     }
 
     // the view defining the layout, linking the widgets to the interaction 'message' enum
-    fn view(&mut self) -> Element<Message> {
-        let btn_txt = Text::new("Count")
-            .horizontal_alignment(HorizontalAlignment::Center);
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Message> {
+        let narrow = size.width < NARROW;
+        let model = &self.model;
+        // the buttons show the step they count by, once it is valid
+        let step = model.step().map(|step| step.to_string()).unwrap_or_default();
+
+        // the debugger can rewind to fewer or more tallies, so the rows are matched up on every view
+        self.rows.resize_with(model.tallies().len(), TallyRow::default);
+        let tallies = self.rows.iter_mut()
+            .enumerate()
+            .fold(Column::new().spacing(SPACING / 2), |list, (index, row)| {
+                list.push(row.view(index, model, &step, narrow, theme))
+            });
+        let tallies = Scrollable::new(&mut self.scroll)
+            .height(Length::Fill)
+            .push(tallies)
+            .style(theme::Scrollable(*theme));
+
+        let total = Row::new()
+            .push(Text::new(tr("counter-total")).size(theme.text_size).width(Length::Fill))
+            .push(Text::new(model.total().to_string())
+                .size(theme.text_size)
+                .width(Length::Units(theme.text(VALUE_WIDTH)))
+                .horizontal_alignment(HorizontalAlignment::Center));
+
+        let reset = model.selected()
+            .filter(|&index| model.tallies()[index].value != model.start())
+            .map(|_| counter::Message::Reset);
+        let controls = Row::new()
+            .spacing(SPACING)
+            .push(button(&mut self.add, tr("counter-add"), Some(counter::Message::Add), theme).width(Length::Fill))
+            .push(button(&mut self.reset, tr("counter-reset"), reset, theme).width(Length::Fill))
+            .push(button(&mut self.clear, tr("counter-clear"), Some(counter::Message::Clear), theme).width(Length::Fill));
+
+        // the rate and sparkline follow the selected tally
+        let (rate, timeline) = match model.selected() {
+            Some(index) => (model.rate(index, self.now), model.timeline(index)),
+            None => (0, Vec::new()),
+        };
+        let undo = model.can_undo().then_some(counter::Message::Undo);
+        let redo = model.can_redo().then_some(counter::Message::Redo);
+        let history = Row::new()
+            .spacing(SPACING)
+            .align_items(Align::Center)
+            .push(button(&mut self.undo, tr("counter-undo"), undo, theme).width(Length::Shrink))
+            .push(button(&mut self.redo, tr("counter-redo"), redo, theme).width(Length::Shrink))
+            .push(Text::new(tr_with("counter-rate", &[("rate", &rate)])).size(theme.text_size));
+        let sparkline = Canvas::new(Sparkline::new(&timeline, self.now, theme))
+            .width(Length::Fill)
+            .height(Length::Units(theme.text(SPARKLINE_HEIGHT)));
+
+        let history: Element<'_, Message> = match narrow {
+            true => Column::new().spacing(SPACING / 2).push(history).push(sparkline).into(),
+            false => history.push(sparkline).into(),
+        };
+
+        let field = |state, label: String, placeholder: &str, value: &str, valid: bool, message: fn(String) -> counter::Message| {
+            Row::new()
+                .spacing(SPACING / 2)
+                .align_items(Align::Center)
+                .push(Text::new(label).size(theme.text_size))
+                .push(TextInput::new(state, placeholder, value, move |s| Message::Model(message(s)))
+                    .padding(3)
+                    .size(theme.text_size)
+                    .style(theme::TextInput(*theme, if valid { Input::Enabled } else { Input::Invalid })))
+        };
+
+        let no_bound = tr("counter-no-bound");
+        let step_field = field(&mut self.step, tr("counter-step"), "1", model.step_text(),
+                               model.step().is_some(), counter::Message::StepUpdated);
+        let min_field = field(&mut self.min, tr("counter-min"), &no_bound, model.min_text(),
+                              model.min_is_valid(), counter::Message::MinUpdated);
+        let max_field = field(&mut self.max, tr("counter-max"), &no_bound, model.max_text(),
+                              model.max_is_valid(), counter::Message::MaxUpdated);
+
+        let settings: Element<'_, Message> = match narrow {
+            true => Column::new()
+                .spacing(SPACING / 2)
+                .align_items(Align::End)
+                .push(step_field)
+                .push(min_field)
+                .push(max_field)
+                .into(),
+            false => Row::new()
+                .spacing(SPACING)
+                .push(step_field)
+                .push(min_field)
+                .push(max_field)
+                .into(),
+        };
+
+        let file = Row::new()
+            .spacing(SPACING / 2)
+            .align_items(Align::Center)
+            .push(TextInput::new(&mut self.file_input, &tr("counter-file"), &self.file, Message::FileUpdated)
+                .padding(3)
+                .size(theme.text_size)
+                .style(theme::TextInput(*theme, Input::Enabled)))
+            .push(Button::new(&mut self.export, Text::new(tr("counter-export")).size(theme.text_size))
+                .style(theme::Button(*theme))
+                .on_press(Message::Export))
+            .push(Button::new(&mut self.import, Text::new(tr("counter-import")).size(theme.text_size))
+                .style(theme::Button(*theme))
+                .on_press(Message::Import));
+
+        let mut content = Column::new()
+            .padding(if narrow { PAD_NARROW } else { PAD })
+            .spacing(SPACING)
+            .push(tallies)
+            .push(total)
+            .push(controls)
+            .push(history)
+            .push(settings)
+            .push(file);
+
+        if let Some(outcome) = &self.outcome {
+            let (text, color) = match outcome {
+                Ok(text) => (text, theme.palette.text_muted),
+                Err(error) => (error, theme.palette.invalid),
+            };
+            content = content.push(Text::new(text.as_str()).size(theme.text_size).color(color));
+        }
+
+        content.into()
+    }
+
+}
+
+impl Counter {
+    // the board is saved after every change, so closing the window or a crash loses nothing.
+    // A failure is shown rather than stopping the counter
+    fn save(&mut self) {
+        if !self.keeps_board {
+            return;
+        }
+        let saved = config::data_path(STATE_FILE)
+            .ok_or_else(|| "no data directory could be found to save the counter in".to_string())
+            .and_then(|path| {
+                let text = serde_json::to_string_pretty(&self.model).map_err(|e| format!("the counter could not be saved: {}", e))?;
+                config::write_atomic(&path, &text)
+            });
+
+        if let Err(error) = saved {
+            self.outcome = Some(Err(error));
+        }
+    }
+
+    // the format is chosen by the file's extension
+    fn read(&self) -> Result<Vec<Tally>, String> {
+        let path = Path::new(self.file.trim());
+        let format = format(path)?;
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{} could not be read: {}", path.display(), e))?;
+
+        match format {
+            Format::Csv => counter::from_csv(&text),
+            Format::Json => counter::from_json(&text),
+        }.map_err(|e| format!("{} is not a tally board: {}", path.display(), e))
+    }
+
+    fn write(&self) -> Result<String, String> {
+        let path = Path::new(self.file.trim());
+        let text = match format(path)? {
+            Format::Csv => self.model.to_csv(),
+            Format::Json => self.model.to_json(),
+        };
+
+        std::fs::write(path, text).map_err(|e| format!("{} could not be written: {}", path.display(), e))?;
+        Ok(tr_with("counter-exported", &[("count", &self.model.tallies().len()), ("file", &path.display())]))
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        if self.keeps_board {
+            KEEPS_BOARD.store(false, Ordering::Release);
+        }
+    }
+}
+
+// the board saved by an earlier run, None when there is none or --fresh was given
+fn load() -> Result<Option<counter::Counter>, String> {
+    let path = match config::data_path(STATE_FILE) {
+        Some(path) if path.exists() && !FRESH.load(Ordering::Relaxed) => path,
+        _ => return Ok(None),
+    };
+
+    let text = std::fs::read_to_string(&path).map_err(|e| format!("{} could not be read: {}", path.display(), e))?;
+    counter::Counter::from_state(&text)
+        .map(Some)
+        .map_err(|e| format!("{} is not a saved counter, an empty board is used instead: {}", path.display(), e))
+}
+
+enum Format {
+    Csv,
+    Json,
+}
+
+fn format(path: &Path) -> Result<Format, String> {
+    match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+        Some("csv") => Ok(Format::Csv),
+        Some("json") => Ok(Format::Json),
+        _ => Err(format!("{} should be a .csv or .json file", path.display())),
+    }
+}
+
+// a button which is disabled without a message, as where counting would pass a bound or overflow
+fn button<'a>(state: &'a mut button::State, label: String, message: Option<counter::Message>, theme: &Theme)
+    -> Button<'a, Message> {
+    let button = Button::new(state, Text::new(label)
+        .size(theme.text_size)
+        .horizontal_alignment(HorizontalAlignment::Center)
+        .width(Length::Fill))
+        .style(theme::Button(*theme));
+
+    match message {
+        Some(message) => button.on_press(Message::Model(message)),
+        None => button,
+    }
+}
 
-        Row::new()
-            .padding(PAD)
-            .spacing(PAD*2)
+
+// ## A single tally
+// Its value is a button selecting it, the selected tally is the one the keyboard counts
+#[derive(Default)]
+struct TallyRow {
+    name: text_input::State,
+    select: button::State,
+    decrement: button::State,
+    increment: button::State,
+    up: button::State,
+    down: button::State,
+    remove: button::State,
+}
+
+impl TallyRow {
+    fn view(&mut self, index: usize, model: &counter::Counter, step: &str, narrow: bool, theme: &Theme)
+        -> Element<'_, Message> {
+        let tally = &model.tallies()[index];
+        let last = index + 1 == model.tallies().len();
+
+        let name = TextInput::new(&mut self.name, &tr("counter-name"), &tally.name,
+                                  move |name| Message::Model(counter::Message::Rename(index, name)))
+            .padding(3)
+            .size(theme.text_size)
+            .style(theme::TextInput(*theme, Input::Enabled));
+
+        let value = Button::new(&mut self.select, Text::new(tally.value.to_string())
+            .size(theme.text_size)
+            .horizontal_alignment(HorizontalAlignment::Center)
+            .width(Length::Fill))
+            .width(Length::Units(theme.text(VALUE_WIDTH)))
+            .style(theme::ListItem(*theme, model.selected() == Some(index)))
+            .on_press(Message::Model(counter::Message::Select(index)));
+
+        let controls = Row::new()
+            .spacing(SPACING / 2)
             .align_items(Align::Center)
-            .push(
-                Text::new(&self.value.to_string())
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::FillPortion(2)),
-            )
-            .push(
-                Button::new(&mut self.btn, btn_txt)
-                    .width(Length::FillPortion(2))
-                    .on_press(Message::ButtonPressed),
-            )
-            .into()
+            .push(button(&mut self.decrement, format!("-{}", step),
+                         model.decremented(index).map(|_| counter::Message::DecrementAt(index)), theme))
+            .push(value)
+            .push(button(&mut self.increment, format!("+{}", step),
+                         model.incremented(index).map(|_| counter::Message::IncrementAt(index)), theme))
+            .push(button(&mut self.up, tr("counter-move-up"),
+                         Some(counter::Message::MoveUp(index)).filter(|_| index > 0), theme))
+            .push(button(&mut self.down, tr("counter-move-down"),
+                         Some(counter::Message::MoveDown(index)).filter(|_| !last), theme))
+            .push(button(&mut self.remove, tr("counter-remove"), Some(counter::Message::Remove(index)), theme));
+
+        match narrow {
+            true => Column::new().spacing(SPACING / 2).push(name).push(controls).into(),
+            false => Row::new().spacing(SPACING).align_items(Align::Center).push(name).push(controls).into(),
+        }
+    }
+}
+
+// ## Sparkline
+// A tally's value over the changes which can still be undone, up to now. The value only changes at
+// a change, so it is drawn as steps between the lowest and highest value
+struct Sparkline {
+    // seconds before now, with the value from then on
+    points: Vec<(f32, i32)>,
+    line: Color,
+    baseline: Color,
+}
+
+impl Sparkline {
+    fn new(timeline: &[(DateTime<Utc>, i32)], now: DateTime<Utc>, theme: &Theme) -> Self {
+        let points = timeline.iter()
+            .map(|&(at, value)| (((now - at).num_milliseconds() as f32 / 1000.).max(0.), value))
+            .collect();
+
+        Sparkline {
+            points,
+            line: theme.palette.primary,
+            baseline: theme.palette.border,
+        }
     }
+}
 
+impl canvas::Program<Message> for Sparkline {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let mut frame = Frame::new(bounds.size());
+        let (width, height) = (frame.width(), frame.height());
+        frame.stroke(&Line::line(Point::new(0., height), Point::new(width, height)),
+                     Stroke::default().with_color(self.baseline));
+
+        let low = self.points.iter().map(|&(_, value)| value).min();
+        let high = self.points.iter().map(|&(_, value)| value).max();
+        if let (Some(&(span, _)), Some(low), Some(high)) = (self.points.first(), low, high) {
+            // the line is kept clear of the edges so it isn't cut in half at the lowest and highest value
+            let margin = 2.;
+            let x = |age: f32| if span > 0. { width * (1. - age / span) } else { width };
+            let y = |value: i32| match high > low {
+                true => height - margin - (f64::from(value) - f64::from(low)) as f32
+                    / (f64::from(high) - f64::from(low)) as f32 * (height - 2. * margin),
+                false => height / 2.,
+            };
+
+            let line = Line::new(|path| {
+                let mut last = self.points[0].1;
+                path.move_to(Point::new(x(span), y(last)));
+                for &(age, value) in &self.points[1..] {
+                    path.line_to(Point::new(x(age), y(last)));
+                    path.line_to(Point::new(x(age), y(value)));
+                    last = value;
+                }
+                path.line_to(Point::new(width, y(last)));
+            });
+            frame.stroke(&line, Stroke::default().with_color(self.line).with_width(1.5));
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+// Keys typed while no text input has them
+fn shortcut(event: iced_native::Event, status: iced_native::event::Status) -> Option<Message> {
+    use iced_native::{event::Status, keyboard, keyboard::KeyCode, Event};
+
+    let message = match (event, status) {
+        (Event::Keyboard(keyboard::Event::KeyPressed { key_code, modifiers }), Status::Ignored) if modifiers.control => {
+            match (key_code, modifiers.shift) {
+                (KeyCode::Z, false) => counter::Message::Undo,
+                (KeyCode::Z, true) | (KeyCode::Y, _) => counter::Message::Redo,
+                _ => return None,
+            }
+        },
+        // control characters come with Ctrl, those keys were handled above
+        (Event::Keyboard(keyboard::Event::CharacterReceived(c)), Status::Ignored) => match c {
+            '+' => counter::Message::Increment,
+            '-' => counter::Message::Decrement,
+            '0' => counter::Message::Reset,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Message::Model(message))
 }
@@ -1,217 +1,171 @@
-use iced::{button, Button, Row, Element, Sandbox, Settings, Text, scrollable, Scrollable,
-           HorizontalAlignment, Length, Column, Container, text_input, TextInput, Align, Radio};
+use iced::{button, window, Button, Row, Element, Text, scrollable, Scrollable,
+           HorizontalAlignment, Length, Column, Container, text_input, TextInput, Align, Radio, Size};
+use sevenguis_model::crud::{self, List};
+use crate::locale::tr;
+use crate::task::{GuiTask, Preferences, Status};
+use crate::theme::{self, Input, Theme};
 
+pub use crud::Message;
 
-const DEFAULT_SIZE: (u32, u32) = (450, 250);
-const MIN_SIZE: (u32, u32) = (400, 200);
+
+const DEFAULT_SIZE: (u32, u32) = (450, 260);
+const MIN_SIZE: (u32, u32) = (280, 260);
+// below this width the name fields go under the list rather than beside it
+const NARROW: f32 = 400.;
 const PAD: u16 = 10;
 const SPACING: u16 = 5;
 const PAD_SMALL: u16 = 2;
 const TEXTINPUT_WIDTH: u16 = 100;
 
 
-pub fn main() -> iced::Result{
-    // Set the window properties
-    let mut settings = Settings::default();
-    settings.window.size = DEFAULT_SIZE;
-    settings.window.min_size = Some(MIN_SIZE);
-
-    // run the application
-    CRUD::run(settings)
-}
-
-
-struct ListItem {
-    index: usize,
-    label: String,
-}
-
-impl ListItem {
-    fn new(index: usize, label: &str) -> Self {
-        ListItem {
-            index,
-            label: label.to_string()
-        }
-    }
-}
-
-
-struct List {
-    items: Vec<ListItem>,
-    selected: Option<usize>,
+// The scrollable list of names, the names themselves are kept in the model
+#[derive(Default)]
+struct ListView {
     scroll: scrollable::State,
 }
 
-impl Default for List {
-    fn default() -> List {
-        let v = vec![
-            "Emil, Hans",
-            "Mustermann, Max",
-            "Tisch, Roman",
-        ];
-
-        List {
-            items: v.iter()
-                    .enumerate()
-                    .map(
-                        |(i, s)| ListItem::new(i, s)
-                    ).collect(),
-            selected: None,
-            scroll: scrollable::State::default()
-        }
-    }
-}
-
-impl List {
-    fn view(&mut self, filter: &str) -> Container<Message> {
+impl ListView {
+    fn view<'a>(&'a mut self, list: &'a List, filter: &str, theme: &Theme) -> Container<'a, Message> {
 
-        let iterlist = self.items
-            .iter()
-            .filter(|i| i.label.to_lowercase().contains(&filter.to_lowercase()));
+        let iterlist = list.filtered(filter);
 
         let content = iterlist.fold(
             Column::new()
                 .padding(PAD)
                 .spacing(1),
-            |column, item| {
+            |column, (index, label)| {
                 column.push(Radio::new(
-                    item.index,
-                    &item.label,
-                    self.selected,
-                    Message::SelectionChanged))
-                // column.push(Text::new(item))
+                    index,
+                    label,
+                    list.selected(),
+                    Message::SelectionChanged)
+                    .text_size(theme.text_size)
+                    .style(theme::Radio(*theme)))
             }
         );
 
 
 
         let content = Scrollable::new(&mut self.scroll)
-            .push(content.width(Length::Fill));
-
-        Container::new(content).style(style::ListView)
-    }
-
-    fn push(&mut self, label: &str) {
-        let index = self.items.len();
-        let item = ListItem::new(index, label);
-        self.items.push(item);
-    }
-
-    fn remove(&mut self) {
-        if let Some(i) = self.selected {
-            self.items.remove(i);
-            self.items = self.items.iter()
-                .enumerate()
-                .map(|(j, item)| ListItem::new(j, &item.label))
-                .collect();
-            self.selected = None;
-        }
-    }
+            .push(content.width(Length::Fill))
+            .style(theme::Scrollable(*theme));
 
-    fn replace(&mut self, label: &str) {
-        if let Some(i) = self.selected {
-            let item = ListItem::new(i, label);
-            self.items[i] = item;
-        }
+        Container::new(content).style(theme::Panel(*theme))
     }
 }
 
 
-#[derive(Debug, Clone)]
-enum Message {
-    CreatePressed,
-    UpdatePressed,
-    DeletePressed,
-    FilterUpdated(String),
-    NameUpdated(String),
-    SurnameUpdated(String),
-    SelectionChanged(usize),
-}
-
-
 // To simplify the data model fields
 #[derive(Default)]
-struct InputData {
-    value: String,
-    state: text_input::State
+struct InputStates {
+    filter: text_input::State,
+    name: text_input::State,
+    surname: text_input::State,
 }
 
 
 #[derive(Default)]
-struct CRUD {
-    names: List,
-    input_filter: InputData,
-    input_name: InputData,
-    input_surname: InputData,
+pub struct Crud {
+    model: crud::Crud,
+    list: ListView,
+    inputs: InputStates,
     btn_create: button::State,
     btn_update: button::State,
     btn_delete: button::State,
 }
 
-impl CRUD {
-    fn make_name(&self) -> String {
-        format!("{}, {}", self.input_surname.value, self.input_name.value)
-    }
-}
-
-impl Sandbox for CRUD {
+impl GuiTask for Crud {
     type Message = Message;
+    type Model = crud::Crud;
+
+    const STATUS: Status = Status::Implemented;
+    const THUMBNAIL: &'static [u8] = include_bytes!("../images/CRUD.PNG");
+
+    fn window() -> window::Settings {
+        window::Settings {
+            size: DEFAULT_SIZE,
+            min_size: Some(MIN_SIZE),
+            ..window::Settings::default()
+        }
+    }
 
     fn new() -> Self {
         Self::default()
     }
 
-    fn title(&self) -> String {
-        String::from("C.R.U.D.")
+    // the view already speaks the model's messages
+    fn model_message(&self, message: &Message) -> Option<Message> {
+        Some(message.clone())
+    }
+
+    fn model(&self) -> &Self::Model {
+        &self.model
+    }
+
+    fn model_mut(&mut self) -> &mut Self::Model {
+        &mut self.model
     }
 
     fn update(&mut self, message:Message) {
-        match message {
-            Message::FilterUpdated(s) => self.input_filter.value = s,
-            Message::NameUpdated(s) => self.input_name.value = s,
-            Message::SurnameUpdated(s) => self.input_surname.value = s,
-            Message::SelectionChanged(i) => self.names.selected = Some(i),
-            Message::CreatePressed => self.names.push(&self.make_name()),
-            Message::UpdatePressed => self.names.replace(&self.make_name()),
-            Message::DeletePressed =>  self.names.remove(),
-        }
+        self.model.update(message)
+    }
+
+    fn restore(&self, preferences: &Preferences) -> Vec<Message> {
+        preferences.get("filter")
+            .and_then(|v| v.as_str())
+            .map(|filter| Message::FilterUpdated(filter.to_string()))
+            .into_iter()
+            .collect()
+    }
+
+    fn preferences(&self) -> Preferences {
+        let mut preferences = Preferences::new();
+        preferences.insert("filter".to_string(), self.model.filter().into());
+        preferences
     }
 
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Message> {
+        // the inputs hold about as many characters whatever size the text is
+        let input_width = theme.text(TEXTINPUT_WIDTH);
 
         let in_filter = TextInput::new(
-                &mut self.input_filter.state,
-                "Filter",
-                &self.input_filter.value,
+                &mut self.inputs.filter,
+                &tr("crud-filter"),
+                self.model.filter(),
                 Message::FilterUpdated
             )
                 .padding(PAD_SMALL)
-                .width(Length::Units(TEXTINPUT_WIDTH));
+                .width(Length::Units(input_width))
+                .size(theme.text_size)
+                .style(theme::TextInput(*theme, Input::Enabled));
 
         let header = Row::new()
             .align_items(Align::Center)
             .spacing(SPACING)
             .height(Length::Shrink)
-            .push(Text::new("Filter Prefix:".to_string()))
+            .push(Text::new(tr("crud-filter-prefix")).size(theme.text_size))
             .push(in_filter);
 
         let btn_create = Button::new(&mut self.btn_create,
-                                 Text::new("Create".to_string())
+                                 Text::new(tr("crud-create"))
+                                         .size(theme.text_size)
                                          .horizontal_alignment(HorizontalAlignment::Center)
                                          .width(Length::Fill)
-        ).on_press(Message::CreatePressed);
+        ).style(theme::Button(*theme)).on_press(Message::CreatePressed);
 
         let btn_update = Button::new(&mut self.btn_update,
-                                 Text::new("Update".to_string())
+                                 Text::new(tr("crud-update"))
+                                         .size(theme.text_size)
                                          .horizontal_alignment(HorizontalAlignment::Center)
                                          .width(Length::Fill)
-        ).on_press(Message::UpdatePressed);
+        ).style(theme::Button(*theme)).on_press(Message::UpdatePressed);
 
         let btn_delete = Button::new(&mut self.btn_delete,
-                                 Text::new("Delete".to_string())
+                                 Text::new(tr("crud-delete"))
+                                         .size(theme.text_size)
                                          .horizontal_alignment(HorizontalAlignment::Center)
                                          .width(Length::Fill)
-        ).on_press(Message::DeletePressed);
+        ).style(theme::Button(*theme)).on_press(Message::DeletePressed);
 
         let footer = Row::new()
             .spacing(SPACING)
@@ -220,34 +174,40 @@ impl Sandbox for CRUD {
             .push(btn_delete)
             .height(Length::Shrink);
 
-        let left = self.names.view(&self.input_filter.value)
+        let left = self.list.view(self.model.names(), self.model.filter(), theme)
             .width(Length::Fill)
             .height(Length::Fill);
 
         let in_name = TextInput::new(
-                &mut self.input_name.state,
-                "Name",
-                &self.input_name.value,
+                &mut self.inputs.name,
+                &tr("crud-name"),
+                self.model.name(),
                 Message::NameUpdated
             )
                 .padding(2)
-                .width(Length::Units(TEXTINPUT_WIDTH));
+                .width(Length::Units(input_width))
+                .size(theme.text_size)
+                .style(theme::TextInput(*theme, Input::Enabled));
 
         let in_surname = TextInput::new(
-                &mut self.input_surname.state,
-                "Surname",
-                &self.input_surname.value,
+                &mut self.inputs.surname,
+                &tr("crud-surname"),
+                self.model.surname(),
                 Message::SurnameUpdated
             )
                 .padding(PAD_SMALL)
-                .width(Length::Units(TEXTINPUT_WIDTH));
+                .width(Length::Units(input_width))
+                .size(theme.text_size)
+                .style(theme::TextInput(*theme, Input::Enabled));
 
         let right1 = Row::new()
-            .push(Text::new("Name: "))
+            .spacing(SPACING)
+            .push(Text::new(tr("crud-name-label")).size(theme.text_size))
             .push(in_name);
 
         let right2 = Row::new()
-            .push(Text::new("Surname: "))
+            .spacing(SPACING)
+            .push(Text::new(tr("crud-surname-label")).size(theme.text_size))
             .push(in_surname);
 
         let right = Column::new()
@@ -256,11 +216,20 @@ impl Sandbox for CRUD {
             .push(right1)
             .push(right2);
 
-        let body = Row::new()
-            .spacing(PAD)
-            .push(left)
-            .push(right)
-            .height(Length::Fill);
+        let body: Element<'_, Message> = match size.width < NARROW {
+            true => Column::new()
+                .spacing(SPACING)
+                .push(left)
+                .push(right)
+                .height(Length::Fill)
+                .into(),
+            false => Row::new()
+                .spacing(PAD)
+                .push(left)
+                .push(right)
+                .height(Length::Fill)
+                .into(),
+        };
 
         Column::new()
             .padding(PAD)
@@ -271,24 +240,3 @@ impl Sandbox for CRUD {
             .into()
     }
 }
-
-
-mod style {
-
-    pub struct ListView;
-
-    use iced::{container, Background, Color};
-
-    impl container::StyleSheet for ListView {
-        fn style(&self) -> container::Style {
-            container::Style {
-                border_color: Color::BLACK,
-                border_width: 2.,
-                border_radius: 5.,
-                background: Some(Background::from(Color::from_rgb(0.95, 0.95, 0.95))),
-                ..container::Style::default()
-            }
-        }
-    }
-}
-
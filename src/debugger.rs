@@ -0,0 +1,250 @@
+// # Debugger
+// A panel toggled with F12 listing the messages handled by the open task. Each message is kept
+// with a copy of the model it produced, so the scrubber can put the model back to any earlier point.
+// The task is paused while it is rewound, resuming discards whatever came after that point
+
+use iced::{button, scrollable, slider, Align, Button, Column, Container, Element, Length, Row, Scrollable,
+           Slider, Space, Text};
+use serde_json::Value;
+
+use crate::locale::{tr, tr_with};
+use crate::task::{DynTask, Snapshot};
+use crate::theme::{self, Theme};
+
+// the oldest messages are forgotten beyond this, the timer alone sends sixty a second
+const MAX_HISTORY: usize = 1000;
+const TEXT_SIZE: u16 = 14;
+const PAD: u16 = 5;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Toggle,
+    // the number of messages to apply, 0 being the model before the first one
+    Seek(u32),
+    Resume,
+}
+
+pub struct Debugger {
+    open: bool,
+    // the history of each task in the registry, from when it was created
+    histories: Vec<Option<History>>,
+    slider: slider::State,
+    messages: scrollable::State,
+    state: scrollable::State,
+    resume: button::State,
+}
+
+impl Debugger {
+    pub fn new(tasks: usize) -> Self {
+        Debugger {
+            open: false,
+            histories: (0..tasks).map(|_| None).collect(),
+            slider: slider::State::default(),
+            messages: scrollable::State::default(),
+            state: scrollable::State::default(),
+            resume: button::State::default(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    // starts the history of a task which has just been created
+    pub fn track(&mut self, index: usize, task: &dyn DynTask) {
+        self.histories[index] = Some(History::new(task));
+    }
+
+    // a paused task is showing an earlier state and must not handle new messages
+    pub fn is_paused(&self, index: usize) -> bool {
+        self.histories[index].as_ref().is_some_and(History::is_paused)
+    }
+
+    // adds a message the task has just handled to its history, by the label it was described with
+    // before it was handled. None for a message which left the model alone
+    pub fn handled(&mut self, index: usize, label: Option<String>, task: &dyn DynTask) {
+        if let (Some(history), Some(label)) = (&mut self.histories[index], label) {
+            history.push(label, task);
+        }
+    }
+
+    // the task is None while the menu is shown
+    pub fn update(&mut self, message: Message, task: Option<(usize, &mut Box<dyn DynTask>)>) {
+        let histories = &mut self.histories;
+        let history = task.and_then(|(index, task)| Some((histories[index].as_mut()?, task.as_mut())));
+
+        match (message, history) {
+            (Message::Toggle, history) => {
+                self.open = !self.open;
+                // closing the debugger returns a rewound task to the present
+                if let (false, Some((history, task))) = (self.open, history) {
+                    history.seek(history.entries.len(), task);
+                }
+            },
+            (Message::Seek(position), Some((history, task))) => history.seek(position as usize, task),
+            (Message::Resume, Some((history, task))) => history.resume(task),
+            (_, None) => (),
+        }
+    }
+
+    // 'state' is the model of the task, taken before the task's own view borrows it
+    pub fn view(&mut self, index: usize, state: Result<Value, String>, theme: Theme) -> Element<'_, Message> {
+        let history = match &mut self.histories[index] {
+            Some(history) => history,
+            None => return Space::new(Length::Shrink, Length::Shrink).into(),
+        };
+        let (position, count) = (history.position, history.entries.len());
+        let text_size = theme.text(TEXT_SIZE);
+
+        let mut resume = Button::new(&mut self.resume, Text::new(tr("debugger-resume")).size(text_size))
+            .style(theme::Button(theme));
+        if history.is_paused() {
+            resume = resume.on_press(Message::Resume);
+        }
+
+        let header = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new(tr_with("debugger-position", &[("position", &position), ("count", &count)])).size(text_size).width(Length::Fill))
+            .push(resume);
+
+        // the slider needs a range to move in even before the first message
+        let scrubber = Slider::new(&mut self.slider, 0..=count.max(1) as u32, position as u32, Message::Seek)
+            .style(theme::Slider(theme));
+
+        // the newest message is listed first so it is in view without scrolling
+        let start = tr("debugger-start");
+        let messages = history.entries.iter_mut()
+            .enumerate()
+            .rev()
+            .map(|(i, entry)| (i + 1, entry.label.as_str(), &mut entry.button))
+            .chain(std::iter::once((0, start.as_str(), &mut history.start)))
+            .fold(Scrollable::new(&mut self.messages).width(Length::FillPortion(1)), |list, (i, label, state)| {
+                list.push(Button::new(state, Text::new(label).size(text_size))
+                    .width(Length::Fill)
+                    .style(theme::ListItem(theme, i == position))
+                    .on_press(Message::Seek(i as u32)))
+            })
+            .style(theme::Scrollable(theme));
+
+        let lines = match state {
+            Ok(value) => {
+                let mut lines = Vec::new();
+                tree(None, &value, 0, &mut lines);
+                lines
+            },
+            Err(error) => vec![error],
+        };
+        let state = lines.into_iter()
+            .fold(Scrollable::new(&mut self.state).width(Length::FillPortion(1)), |tree, line| {
+                tree.push(Text::new(line).size(text_size))
+            })
+            .style(theme::Scrollable(theme));
+
+        Container::new(Column::new()
+            .spacing(PAD)
+            .push(header)
+            .push(scrubber)
+            .push(Row::new().spacing(PAD).height(Length::Fill).push(messages).push(state)))
+            .padding(PAD)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(theme::Panel(theme))
+            .into()
+    }
+}
+
+
+// ## The messages handled by a single task
+struct History {
+    // the model before the first message kept
+    base: Snapshot,
+    entries: Vec<Entry>,
+    // how many of the entries the model shown has been through, fewer than all of them while rewound
+    position: usize,
+    start: button::State,
+}
+
+struct Entry {
+    label: String,
+    // the model after the message was handled
+    snapshot: Snapshot,
+    button: button::State,
+}
+
+impl History {
+    fn new(task: &dyn DynTask) -> Self {
+        History {
+            base: task.snapshot(),
+            entries: Vec::new(),
+            position: 0,
+            start: button::State::default(),
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.position < self.entries.len()
+    }
+
+    fn push(&mut self, label: String, task: &dyn DynTask) {
+        self.entries.push(Entry {
+            label,
+            snapshot: task.snapshot(),
+            button: button::State::default(),
+        });
+
+        if self.entries.len() > MAX_HISTORY {
+            self.base = self.entries.remove(0).snapshot;
+        }
+        self.position = self.entries.len();
+    }
+
+    fn seek(&mut self, position: usize, task: &mut dyn DynTask) {
+        self.position = position.min(self.entries.len());
+        task.rewind(self.snapshot());
+    }
+
+    // continues from the point rewound to, the messages after it are forgotten
+    fn resume(&mut self, task: &mut dyn DynTask) {
+        self.entries.truncate(self.position);
+        task.rewind(self.snapshot());
+    }
+
+    fn snapshot(&self) -> &Snapshot {
+        match self.position {
+            0 => &self.base,
+            position => &self.entries[position - 1].snapshot,
+        }
+    }
+}
+
+// Lays out a value one line per field, nested values are indented under their key
+fn tree(key: Option<&str>, value: &Value, depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(map) if !map.is_empty() => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Value::Array(items) if !items.is_empty() => {
+            items.iter().enumerate().map(|(i, v)| (format!("[{}]", i), v)).collect()
+        },
+        _ => {
+            lines.push(match key {
+                Some(key) => format!("{}{}: {}", indent, key, value),
+                None => format!("{}{}", indent, value),
+            });
+            return;
+        },
+    };
+
+    // the root's fields are not indented under a heading
+    let depth = match key {
+        Some(key) => {
+            lines.push(format!("{}{}", indent, key));
+            depth + 1
+        },
+        None => depth,
+    };
+    for (key, value) in children {
+        tree(Some(&key), value, depth, lines);
+    }
+}
@@ -1,309 +1,223 @@
 // # An application to demonstrate data-validation
 
-use iced::{Align, Button, Element, Sandbox, Settings, Text, HorizontalAlignment, Length, TextInput, Space, PickList, Column};
-use iced::{text_input, pick_list, button};
-use chrono::{Local, NaiveDate};
+use iced::{Align, Button, Element, Text, HorizontalAlignment, Length, TextInput, Space, PickList, Column,
+           Container, Size};
+use iced::{text_input, pick_list, button, window};
+use chrono::Local;
+use sevenguis_model::flights::{self, DateFormat, Field, FlightType};
+use crate::locale::{self, tr, tr_with, Language};
+use crate::task::{GuiTask, Preferences, Status};
+use crate::theme::{self, Input, Theme};
+
+pub use flights::Message;
 
 // ## Constants
-const SIZE: (u32, u32) = (250, 250);
+const DEFAULT_SIZE: (u32, u32) = (250, 250);
+const MIN_SIZE: (u32, u32) = (180, 200);
 const PAD: u16 = 25;
-
-// ## Entry Point
-pub fn main() -> iced::Result{
-    // Set the window properties
-    let mut settings = Settings::default();
-    settings.window.size = SIZE;
-    settings.window.resizable = false;
-
-    Flights::run(settings)
-}
-
-// ## Primary data model
+// the padding shrinks below this width or height so the fields keep their space
+const NARROW: f32 = 220.;
+const SHORT: f32 = 240.;
+const PAD_NARROW: u16 = 10;
+// the fields stop widening here and are centred instead
+const MAX_WIDTH: u32 = 400;
+
+// ## The view over the data model in 'sevenguis_model::flights'
 #[derive(Default)]
-struct Flights{
+pub struct Flights{
+    model: flights::Flights,
     book: button::State,
-    valid: bool,
-    dropdown: pick_list::State<FlightType>,
-    type_selected: FlightType,
-    input_outbound: TextInputData,
-    input_inbound: TextInputData,
+    dropdown: pick_list::State<Trip>,
+    outbound: text_input::State,
+    inbound: text_input::State,
 }
 
-#[derive(Default, Clone)]
-struct TextInputData {
-    state: text_input::State,
-    value: String,
-    style: style::TextInput,
-}
-
-// ## An enum to define potential events (Messages)
-#[derive(Debug, Clone)]
-enum Message{
-    TypeSelected(FlightType),
-    OutboundUpdated(String),
-    InboundUpdated(String),
-    BookRequest,
-}
-
-// ## Dropdown List definitions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum FlightType {
-    OneWay,
-    Return,
-}
+// ## The interface used by the launcher to drive the task
+impl GuiTask for Flights {
+    type Message = Message;
+    type Model = flights::Flights;
 
-impl FlightType {
-    const ALL: [FlightType; 2] = [
-        FlightType::OneWay,
-        FlightType::Return,
-    ];
-}
+    const STATUS: Status = Status::Partial("flights-partial");
+    const THUMBNAIL: &'static [u8] = include_bytes!("../images/Flights.PNG");
 
-impl Default for FlightType {
-    fn default() -> FlightType {
-        FlightType::OneWay
+    fn window() -> window::Settings {
+        window::Settings {
+            size: DEFAULT_SIZE,
+            min_size: Some(MIN_SIZE),
+            ..window::Settings::default()
+        }
     }
-}
 
-impl std::fmt::Display for FlightType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                FlightType::OneWay => "One-way Flight",
-                FlightType::Return => "Return Flight",
-            }
-        )
+    fn new() -> Self {
+        Self::default()
     }
-}
 
-// ## This trait controls the application
-impl Sandbox for Flights {
-    type Message = Message;
+    // the view already speaks the model's messages
+    fn model_message(&self, message: &Message) -> Option<Message> {
+        Some(message.clone())
+    }
 
-    fn new() -> Self {
-        Self::default()
+    fn model(&self) -> &Self::Model {
+        &self.model
     }
 
-    // Sets the window title
-    fn title(&self) -> String {
-        String::from("Flight Booker")
+    fn model_mut(&mut self) -> &mut Self::Model {
+        &mut self.model
     }
 
     // updates the model based on a given message
     fn update(&mut self, message:Message) {
-        match message {
-            Message::TypeSelected(t) => {
-                self.type_selected = t;
-                if self.type_selected == FlightType::Return {
-                    self.input_inbound.style = style::TextInput::Enabled;
-                    self.valid = true;
-                } else {
-                    self.input_inbound.style = style::TextInput::Disabled;
-                }
-            },
-            Message::OutboundUpdated(s) => {
-                self.input_outbound.value = s;
-            },
-            Message::InboundUpdated(s) => {
-                if self.type_selected == FlightType::Return {
-                    self.input_inbound.value = s;
-                }
+        let book_request = message == Message::BookRequest;
+        self.model.update(message);
+
+        if book_request {
+            self.book_flight();
+        }
+    }
+
+    // the date format can only be chosen in the settings file, e.g. date_format = "yyyy-mm-dd",
+    // without one dates are written the way the language writes them
+    fn restore(&self, preferences: &Preferences) -> Vec<Message> {
+        let name = preferences.get("date_format").and_then(|v| v.as_str());
+        let language = locale::current().date_format();
+
+        match name.map(|name| (name, DateFormat::from_name(name))) {
+            Some((_, Some(format))) => vec![Message::DateFormatSelected(format)],
+            Some((name, None)) => {
+                eprintln!("7Guis: unknown flights date format '{}' in the settings", name);
+                vec![Message::DateFormatSelected(language)]
             },
-            Message::BookRequest => self.book_flight(),
+            None => vec![Message::DateFormatSelected(language)],
+        }
+    }
+
+    // the language's own format isn't saved, so it keeps following the language
+    fn preferences(&self) -> Preferences {
+        let mut preferences = Preferences::new();
+        let format = self.model.date_format();
+        if format != locale::current().date_format() {
+            preferences.insert("date_format".to_string(), format.name().into());
+        }
+        preferences
+    }
+
+    // a format chosen in the settings is kept whatever the language
+    fn language_changed(&self, previous: Language, language: Language) -> Vec<Message> {
+        match self.model.date_format() == previous.date_format() {
+            true => vec![Message::DateFormatSelected(language.date_format())],
+            false => Vec::new(),
         }
-        self.update_validity();
     }
 
     // Defines the layout of the application (the view)
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Message> {
+        let pad = match size.width < NARROW || size.height < SHORT {
+            true => PAD_NARROW,
+            false => PAD,
+        };
+
         let dropdown = PickList::new(
             &mut self.dropdown,
-            &FlightType::ALL[..],
-            Some(self.type_selected),
-            Message::TypeSelected,
+            FlightType::ALL.iter().copied().map(Trip).collect::<Vec<_>>(),
+            Some(Trip(self.model.type_selected())),
+            |trip| Message::TypeSelected(trip.0),
         )
-            .width(Length::Fill);
+            .width(Length::Fill)
+            .text_size(theme.text_size)
+            .style(theme::PickList(*theme));
 
         let tbox_outbound = TextInput::new(
-            &mut self.input_outbound.state,
-            &today(),
-            &self.input_outbound.value,
+            &mut self.outbound,
+            &today(self.model.date_format()),
+            self.model.outbound(),
             Message::OutboundUpdated
         )
             .padding(5)
-            .style(self.input_outbound.style);
+            .size(theme.text_size)
+            .style(theme::TextInput(*theme, input(self.model.outbound_field())));
 
         let tbox_inbound = TextInput::new(
-            &mut self.input_inbound.state,
-            &today(),
-            &self.input_inbound.value,
+            &mut self.inbound,
+            &today(self.model.date_format()),
+            self.model.inbound(),
             Message::InboundUpdated
         )
             .padding(5)
-            .style(self.input_inbound.style);
+            .size(theme.text_size)
+            .style(theme::TextInput(*theme, input(self.model.inbound_field())));
 
         let btn_book = Button::new(
             &mut self.book,
-            Text::new("Book")
+            Text::new(tr("flights-book"))
+                .size(theme.text_size)
                 .horizontal_alignment(HorizontalAlignment::Center)
                 .width(Length::Fill)
         )
-            .width(Length::Fill);
+            .width(Length::Fill)
+            .style(theme::Button(*theme));
 
-        Column::new()
-            .padding(PAD)
-            .spacing(PAD/2)
+        let form = Column::new()
+            .padding(pad)
+            .spacing(pad/2)
+            .max_width(MAX_WIDTH)
             .align_items(Align::Center)
             .push(dropdown)
             .push(tbox_outbound)
             .push(tbox_inbound)
             .push(Space::with_height(Length::Fill))
-            .push(if self.valid {
+            .push(if self.model.is_valid() {
                 btn_book.on_press(Message::BookRequest)
             } else {
                 btn_book // when no on_press method is provided so the button is disabled
-            })
+            });
+
+        Container::new(form)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
             .into()
     }
 }
 
 impl Flights {
-    fn update_validity(&mut self) {
-        // 2 inputs need to be checked for validity, initilise as neither being valid
-        let mut valid_outbox = false;
-        let mut valid_inbox = false;
-
-        let date_outbound = NaiveDate::parse_from_str(
-            &self.input_outbound.value,
-            "%d-%m-%Y");
-
-        let date_inbound = NaiveDate::parse_from_str(
-            &self.input_inbound.value,
-            "%d-%m-%Y");
-
-        // Check outbox validity
-        if date_outbound.is_ok() {
-            valid_outbox = true;
-            self.input_outbound.style = style::TextInput::Enabled
-        } else {
-            self.input_outbound.style = style::TextInput::Invalid
-        }
-
-        // check inbox validity
-        if self.type_selected == FlightType::Return {
-            if let Ok(indate) = date_inbound {
-                // the date will parse but may not be valid as it may be before the outbound date
-                if let Ok(outdate) = date_outbound {
-                    let duration = indate.signed_duration_since(outdate).num_days();
-                    // check at least one day has passed
-                    if duration > 0 {
-                        valid_inbox = true;
-                        self.input_inbound.style = style::TextInput::Enabled
-                    } else {
-                        self.input_inbound.style = style::TextInput::Invalid
-                    }
-                }
-            } else {
-                self.input_inbound.style = style::TextInput::Invalid
-            }
-        } else {
-            // if it's a one way flight the inflight is automatically valid
-            valid_inbox = true;
-            self.input_inbound.style = style::TextInput::Disabled
-        }
-
-        self.valid = valid_outbox && valid_inbox;
-    }
-
     fn book_flight(&self) {
-        println!("\nA {} has been booked", self.type_selected);
-        println!("\nDeparting on: {}", self.input_outbound.value);
-        if self.type_selected==FlightType::Return {
-            println!("\nReturning on: {}", self.input_inbound.value);
-        };
-    }
-}
-
-fn today() -> String {
-    Local::today().format("%d-%m-%Y").to_string()
-}
-
-// used to set the style of the text inputs
-mod style {
-    use iced::{text_input, Background, Color};
-
-    // recycleable colours to use between widgets
-    const BACKGROUND_CLR: Background = Background::Color(Color::WHITE);
-    const ACCENT_CLR: Color = Color::from_rgb(0.7, 0.7, 0.7);
-    const TEXT_CLR: Color = Color::from_rgb(0.2, 0.2 ,0.2);
-    const TEXT_CLR_DISABLED: Color = Color::from_rgb(0.8, 0.8 ,0.8);
-    const TEXT_CLR_INVALID: Color = Color::from_rgb(0.8, 0.2 ,0.2);
-    const HIGHLIGHT_CLR: Color = Color::from_rgb(0.8, 0.8, 0.8);
-
-    const BORDER_WIDTH: f32 = 1.;
-    const BORDER_RADIUS: f32 = 5.;
-
-    const DEFAULT_TEXT_INPUT_STYLE: text_input::Style = text_input::Style{
-        background: BACKGROUND_CLR,
-        border_radius: BORDER_RADIUS,
-        border_width: BORDER_WIDTH,
-        border_color: ACCENT_CLR,
-    };
-
-
-    // Defines 3 styles
-    #[derive(Clone, Copy)]
-    pub enum TextInput {
-        Enabled,
-        Disabled,
-        Invalid,
-    }
-
-    impl Default for TextInput {
-        fn default() -> Self {
-            TextInput::Enabled
+        let format = self.model.date_format().pattern();
+        if let Some(booking) = self.model.booking() {
+            println!("\n{}", tr_with("flights-booked", &[("flight", &Trip(booking.flight_type))]));
+            println!("\n{}", tr_with("flights-departing", &[("date", &booking.outbound.format(format))]));
+            if let Some(inbound) = booking.inbound {
+                println!("\n{}", tr_with("flights-returning", &[("date", &inbound.format(format))]));
+            };
         }
     }
+}
 
-    // sets style based on above enum
-    impl text_input::StyleSheet for TextInput {
-
-        fn active(&self) -> text_input::Style {
-            DEFAULT_TEXT_INPUT_STYLE
-        }
-
-        fn focused(&self) -> text_input::Style {
-            match self {
-                TextInput::Disabled => DEFAULT_TEXT_INPUT_STYLE,
-                _ => {
-                    text_input::Style {
-                        border_color: Color::BLACK,
-                        ..DEFAULT_TEXT_INPUT_STYLE
-                    }
-                },
-            }
-        }
-
-        fn placeholder_color(&self) -> Color {
-            TEXT_CLR_DISABLED
-        }
+// A flight type as it is named in the selected language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Trip(FlightType);
 
-        fn value_color(&self) -> Color {
-            match self {
-                TextInput::Enabled => TEXT_CLR,
-                TextInput::Disabled => TEXT_CLR_DISABLED,
-                TextInput::Invalid => TEXT_CLR_INVALID,
+impl std::fmt::Display for Trip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.0 {
+                FlightType::OneWay => tr("flights-one-way"),
+                FlightType::Return => tr("flights-return"),
             }
-        }
+        )
+    }
+}
 
-        fn selection_color(&self) -> Color {
-            match self {
-                TextInput::Disabled => Color::TRANSPARENT,
-                _ => HIGHLIGHT_CLR,
-            }
+fn today(format: DateFormat) -> String {
+    Local::now().format(format.pattern()).to_string()
+}
 
-        }
+// the style of a text input reflects the validation of its field
+fn input(field: Field) -> Input {
+    match field {
+        Field::Valid => Input::Enabled,
+        Field::Invalid => Input::Invalid,
+        Field::Disabled => Input::Disabled,
     }
 }
@@ -0,0 +1,168 @@
+// # Localization
+// Every string shown in the interface is looked up by its id in the message file of the selected
+// language, 'locales/<code>.ftl', written in a subset of Fluent:
+//
+//     # a comment
+//     menu-search = Search
+//     debugger-position = Message { $position } of { $count }
+//
+// A message missing from a language falls back to English, and to its id when English lacks it too.
+// The language can be switched while the launcher runs, so it is kept here rather than handed
+// to every view alongside the theme
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use sevenguis_model::flights::DateFormat;
+
+mod bundle;
+
+use bundle::Bundle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [
+        Language::English,
+        Language::German,
+    ];
+
+    // the code used in the settings and on the command line
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.iter().copied().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    // the language asked for by the environment, e.g. LANG=de_DE.UTF-8
+    pub fn from_environment() -> Option<Language> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Language::from_code(value.split(['_', '-', '.']).next()?))
+    }
+
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Language::English => '.',
+            Language::German => ',',
+        }
+    }
+
+    // the order dates are written in, unless the flight booker has been given one in the settings
+    pub fn date_format(&self) -> DateFormat {
+        match self {
+            Language::English => DateFormat::DayMonthYear,
+            Language::German => DateFormat::DayMonthYearDots,
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+        }
+    }
+}
+
+// each language is named in its own language, so it can be found whichever one is selected
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Language::English => "English",
+                Language::German => "Deutsch",
+            }
+        )
+    }
+}
+
+// the position of the selected language in 'Language::ALL'
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn current() -> Language {
+    Language::ALL[CURRENT.load(Ordering::Relaxed)]
+}
+
+pub fn set(language: Language) {
+    let index = Language::ALL.iter().position(|&l| l == language).unwrap_or_default();
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+// The message with the given id in the selected language
+pub fn tr(id: &str) -> String {
+    tr_with(id, &[])
+}
+
+// The message with the given id, its variables replaced by the values given for them
+pub fn tr_with(id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    static BUNDLES: OnceLock<Vec<Bundle>> = OnceLock::new();
+    let bundles = BUNDLES.get_or_init(|| {
+        Language::ALL.iter().map(|language| Bundle::parse(language.code(), language.source())).collect()
+    });
+
+    let index = CURRENT.load(Ordering::Relaxed);
+    match bundles[index].get(id).or_else(|| bundles[0].get(id)) {
+        Some(pattern) => bundle::format(pattern, args),
+        None => id.to_string(),
+    }
+}
+
+// A number written by a model, with a '.' and no grouping, as the selected language writes it:
+// thousands are grouped with a ',' and then the ',' and '.' swap places where the language uses a decimal comma
+pub fn number(text: &str) -> String {
+    swap_separator(&group(text), current().decimal_separator())
+}
+
+// A number typed in the selected language, as a model expects it. Only the separators swap places,
+// so 'typed_number' gives back exactly what was typed
+pub fn parse_number(text: &str) -> String {
+    swap_separator(text, current().decimal_separator())
+}
+
+// The text of a number read with 'parse_number', as it was typed
+pub fn typed_number(text: &str) -> String {
+    swap_separator(text, current().decimal_separator())
+}
+
+// groups the digits before the point in threes once there are more than three, text which isn't
+// a plain number is left as it is
+fn group(text: &str) -> String {
+    let (sign, unsigned) = text.split_at(if text.starts_with('-') { 1 } else { 0 });
+    let (whole, fraction) = unsigned.split_at(unsigned.find('.').unwrap_or(unsigned.len()));
+    if whole.len() <= 3 || !whole.chars().all(|c| c.is_ascii_digit()) {
+        return text.to_string();
+    }
+
+    let mut grouped = String::from(sign);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped + fraction
+}
+
+fn swap_separator(text: &str, separator: char) -> String {
+    text.chars()
+        .map(|c| match c {
+            '.' => separator,
+            c if c == separator => '.',
+            c => c,
+        })
+        .collect()
+}
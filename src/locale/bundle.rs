@@ -0,0 +1,84 @@
+// # Message files
+// The subset of Fluent the message files are written in: messages on a single line or continued
+// on indented lines below, comments starting with '#', and variables written as '{ $name }'
+
+use std::collections::HashMap;
+use std::fmt;
+
+pub struct Bundle {
+    messages: HashMap<String, String>,
+}
+
+impl Bundle {
+    // the files are compiled in, a line which can't be understood is reported and skipped
+    pub fn parse(name: &str, source: &str) -> Bundle {
+        let mut messages = HashMap::new();
+        let mut last: Option<String> = None;
+
+        for (number, line) in source.lines().enumerate() {
+            let continued = line.starts_with(|c: char| c.is_whitespace());
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match (continued, &last, line.split_once('=')) {
+                (true, Some(id), _) => {
+                    let value: &mut String = messages.get_mut(id).expect("continues a message already read");
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(line);
+                },
+                (false, _, Some((id, value))) if is_identifier(id.trim()) => {
+                    let id = id.trim().to_string();
+                    messages.insert(id.clone(), value.trim().to_string());
+                    last = Some(id);
+                },
+                _ => eprintln!("7Guis: {}.ftl line {}: expected 'id = message', found '{}'", name, number + 1, line),
+            }
+        }
+
+        Bundle { messages }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(String::as_str)
+    }
+}
+
+// ids start with a letter, followed by letters, digits, '-' and '_'
+fn is_identifier(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Replaces the variables in a message, one without a value is left as it was written
+pub fn format(pattern: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => {
+                text.push_str(&rest[start..]);
+                return text;
+            },
+        };
+
+        let placeable = &rest[start..=end];
+        let name = placeable[1..placeable.len() - 1].trim().strip_prefix('$');
+        match name.and_then(|name| args.iter().find(|(arg, _)| *arg == name)) {
+            Some((_, value)) => text.push_str(&value.to_string()),
+            None => text.push_str(placeable),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    text.push_str(rest);
+    text
+}
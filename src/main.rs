@@ -5,96 +5,953 @@ mod temperature;
 mod flights;
 mod timer;
 mod crud;
+mod cli;
+mod config;
+mod debugger;
+mod locale;
+mod record;
+mod scale;
+mod settings;
+mod task;
+mod theme;
+mod workspace;
 
-use iced::{button, Settings, Button, Column, Sandbox, Element, Text, HorizontalAlignment, Length};
+use iced::{button, executor, image, Size, pane_grid, pick_list, scrollable, text_input, time, window, Image, Scrollable, Space, Settings, Button, Column, Row, Application, Command,
+           Subscription, Element, Text, HorizontalAlignment, Length, Align, Color, Container, PickList, TextInput};
+use iced_native::keyboard::KeyCode;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use locale::{tr, tr_with, Language};
+use scale::{Scale, TextSize};
+use task::{DynTask, Entry, Status, TaskMessage, PLANNED, REGISTRY};
+use theme::{Preset, Theme};
 
-fn main() -> iced::Result {
-    let mut settings = Settings::default();
-    settings.window.size = (200, 400);
-    settings.window.resizable = false;
-    Landing::run(settings)
+const MENU_SIZE: (u32, u32) = (480, 640);
+// the width of the screenshots on the task cards in the menu
+const THUMBNAIL_WIDTH: u16 = 120;
+const PAD: u16 = 5;
+// height reserved for the navigation bar shown above an open task
+const NAV_HEIGHT: u16 = 40;
+// how often the theme file is checked for changes while the custom theme is selected
+const THEME_POLL: Duration = Duration::from_secs(1);
+
+fn main() {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("7Guis: {}\n\n{}", error, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // the launcher's language is settled once the settings are read, --list has only the environment to go on
+    locale::set(Language::from_environment().unwrap_or_default());
+
+    let options = match command {
+        cli::Command::Help => return println!("{}", cli::USAGE),
+        cli::Command::List => {
+            for entry in REGISTRY {
+                println!("{:<12} {}", entry.name, entry.description());
+            }
+            return;
+        },
+        cli::Command::Replay(path) => {
+            return match replay(&path) {
+                Ok(state) => println!("{}", state),
+                Err(error) => {
+                    eprintln!("7Guis: {}", error);
+                    std::process::exit(1);
+                }
+            };
+        },
+        cli::Command::Launch(options) => options,
+    };
+
+    // the application still starts with the defaults when the saved settings can't be read
+    let (saved, error) = match settings::Settings::load() {
+        Ok(saved) => (saved, None),
+        Err(error) => {
+            eprintln!("7Guis: {}", error);
+            (settings::Settings::default(), Some(error))
+        }
+    };
+
+    // options given on the command line take precedence over the saved ones
+    let saved_theme = saved.theme.as_deref().and_then(Preset::from_name);
+    let window_key = options.task.map_or(settings::LAUNCHER, |entry| entry.name);
+    let saved_size = saved.windows.get(window_key).copied();
+    let scale = options.scale.or_else(|| saved.scale.and_then(Scale::new)).unwrap_or_default();
+    let text_size = options.text_size.or_else(|| saved.text_size.and_then(TextSize::new)).unwrap_or_default();
+    let saved_language = saved.language.as_deref().and_then(Language::from_code);
+    if let Some(language) = options.language.or(saved_language) {
+        locale::set(language);
+    }
+    if options.fresh {
+        counter::start_fresh();
+    }
+
+    let mut settings = Settings::with_flags(Flags {
+        task: options.task,
+        theme: options.theme.or(saved_theme).unwrap_or_default(),
+        scale,
+        text_size,
+        error,
+        saved,
+        record: options.record,
+        // set once the window's size is known
+        window: (0, 0),
+    });
+    settings.window = match options.task {
+        Some(entry) => (entry.window)(),
+        None => launcher_window(),
+    };
+    settings.antialiasing = true;
+    // the tasks give their sizes unscaled, a saved size is already the size the window was
+    settings.window.size = scale.window(settings.window.size);
+    settings.window.min_size = settings.window.min_size.map(|size| scale.window(size));
+
+    let (width, height) = saved_size.unwrap_or(settings.window.size);
+    settings.window.size = (options.width.unwrap_or(width), options.height.unwrap_or(height));
+    settings.flags.window = settings.window.size;
+    if let Some(resizable) = options.resizable {
+        settings.window.resizable = resizable;
+    }
+
+    // a task launched directly has no launcher to fall back on
+    let result = match options.task {
+        Some(_) => Landing::run(settings).map_err(|e| describe(&e)),
+        None => launch(settings),
+    };
+
+    if let Err(error) = result {
+        eprintln!("7Guis: {}", error);
+        std::process::exit(1);
+    }
+}
+
+// Replays a recording made with '--record', giving the final state of each task as JSON
+fn replay(path: &Path) -> Result<String, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("{} could not be opened: {}", path.display(), e))?;
+    let state = sevenguis_model::replay::replay(BufReader::new(file))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    serde_json::to_string_pretty(&state).map_err(|e| e.to_string())
 }
 
+// A single window is shared by the menu and every task, so it is sized to fit the largest
+fn launcher_window() -> window::Settings {
+    let windows: Vec<window::Settings> = REGISTRY.iter().map(|entry| (entry.window)()).collect();
+
+    let size = windows.iter().fold(MENU_SIZE, |(w, h), window| {
+        (w.max(window.size.0), h.max(window.size.1 + u32::from(NAV_HEIGHT)))
+    });
+    let min_size = windows.iter().filter_map(|window| window.min_size).fold(None, |min, (task_w, task_h)| {
+        let (w, h) = min.unwrap_or((0, 0));
+        Some((w.max(task_w), h.max(task_h + u32::from(NAV_HEIGHT))))
+    });
+
+    window::Settings {
+        size,
+        min_size,
+        ..window::Settings::default()
+    }
+}
 
-#[derive(Default)]
+// Runs the launcher, if the window or renderer can't be created with the requested settings
+// it is retried once without antialiasing and the original failure is shown in a banner
+fn launch(settings: Settings<Flags>) -> Result<(), String> {
+    let mut fallback = settings.clone();
+
+    match Landing::run(settings) {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            let error = describe(&error);
+            eprintln!("7Guis: {}, retrying without antialiasing", error);
+
+            fallback.antialiasing = false;
+            fallback.flags.error = Some(error);
+            Landing::run(fallback).map_err(|e| describe(&e))
+        }
+    }
+}
+
+// iced only displays a generic message, the underlying cause is appended where there is one
+fn describe(error: &iced::Error) -> String {
+    match error {
+        iced::Error::WindowCreationFailed(cause) => format!("{}: {}", error, cause),
+        iced::Error::ExecutorCreationFailed(cause) => format!("{}: {}", error, cause),
+        iced::Error::GraphicsAdapterNotFound => error.to_string(),
+    }
+}
+
+
+// Passed to the launcher when it is created
+#[derive(Debug, Clone, Default)]
+struct Flags {
+    // a task to open on its own, without the menu
+    task: Option<&'static Entry>,
+    theme: Preset,
+    scale: Scale,
+    text_size: TextSize,
+    // a failure to report as soon as the launcher is shown
+    error: Option<String>,
+    // what was remembered from the last run, written back when the launcher closes
+    saved: settings::Settings,
+    // where to record the messages handled by the tasks
+    record: Option<PathBuf>,
+    // the size the window is opened at
+    window: (u32, u32),
+}
+
+
+// Tasks are referred to by their index in the registry. A task is created the first time it is
+// opened and kept for the lifetime of the launcher, so switching back to it resumes where it was left
 struct Landing{
-    counter: button::State,
-    temperature: button::State,
-    flights: button::State,
-    timer: button::State,
-    crud: button::State,
-    circle: button::State,
-    cells: button::State,
+    active: Option<usize>,
+    // set when a task was launched directly, there is no menu to go back to
+    standalone: bool,
+    error: Option<String>,
+    theme: Preset,
+    // the last valid theme read from the theme file
+    custom: Option<Theme>,
+    theme_file: theme::file::Watcher,
+    // what is wrong with the theme file, kept apart from 'error' as it clears once the file is fixed
+    theme_error: Option<String>,
+    scale: Scale,
+    text_size: TextSize,
+    dismiss: button::State,
+    back: button::State,
+    menu: Menu,
+    tasks: Vec<Option<Box<dyn DynTask>>>,
+    // the workspace is shown instead of the menu or a task while this is set
+    in_workspace: bool,
+    workspace: workspace::Workspace,
+    saved: settings::Settings,
+    recorder: Option<record::Recorder>,
+    debugger: debugger::Debugger,
+    // kept up to date as the window is resized, so tasks can fit their layout to it.
+    // This is the size of the window itself, before the interface is scaled
+    window: Size,
 }
 
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 enum Message{
-    Counter,
-    Temperature,
-    Flights,
-    Timer,
-    CRUD,
-    Circle,
-    Cells,
+    Open(usize),
+    OpenWorkspace,
+    Back,
+    DismissError,
+    ThemeSelected(Preset),
+    ScaleSelected(Scale),
+    LanguageSelected(Language),
+    TextSizeSelected(TextSize),
+    CheckThemeFile,
+    WindowResized(u32, u32),
+    Debug(debugger::Message),
+    Workspace(workspace::Message),
+    SearchChanged(String),
+    Key(Key),
+    Task(usize, TaskMessage),
 }
 
-impl Sandbox for Landing {
+// Keys used to get around the launcher without a mouse
+#[derive(Debug, Clone, Copy)]
+enum Key {
+    Up,
+    Down,
+    Enter,
+    Escape,
+    // a number key, opening the task at that position in the registry
+    Hotkey(usize),
+    // a character typed outside the search field, which starts a search
+    Type(char),
+}
+
+impl Application for Landing {
+    type Executor = executor::Default;
     type Message = Message;
+    type Flags = Flags;
+
+    fn new(flags: Flags) -> (Self, Command<Message>) {
+        let (recorder, record_error) = match flags.record.as_deref().map(record::Recorder::create) {
+            Some(Ok(recorder)) => (Some(recorder), None),
+            Some(Err(error)) => {
+                eprintln!("7Guis: {}", error);
+                (None, Some(error))
+            },
+            None => (None, None),
+        };
+
+        let workspace = workspace::Workspace::new(flags.saved.workspace.as_ref());
+
+        let mut landing = Landing {
+            active: None,
+            standalone: flags.task.is_some(),
+            error: flags.error.or(record_error),
+            theme: Preset::default(),
+            custom: None,
+            theme_file: theme::file::Watcher::default(),
+            theme_error: None,
+            scale: flags.scale,
+            text_size: flags.text_size,
+            dismiss: button::State::default(),
+            back: button::State::default(),
+            menu: Menu::default(),
+            tasks: REGISTRY.iter().map(|_| None).collect(),
+            in_workspace: false,
+            workspace,
+            saved: flags.saved,
+            recorder,
+            debugger: debugger::Debugger::new(REGISTRY.len()),
+            window: Size::new(flags.window.0 as f32, flags.window.1 as f32),
+        };
+
+        landing.select_theme(flags.theme);
+
+        // the launcher reopens the task or workspace that was open when it was last closed
+        let last = landing.saved.last_task.clone();
+        match (flags.task, last.as_deref()) {
+            (Some(entry), _) => landing.open(task::index(entry.name).expect("tasks are launched from the registry")),
+            (None, Some(settings::WORKSPACE)) => landing.open_workspace(),
+            (None, Some(name)) => {
+                if let Some(index) = task::index(name) {
+                    landing.open(index)
+                }
+            },
+            (None, None) => (),
+        }
+
+        (landing, Command::none())
+    }
+
+    fn title(&self) -> String {
+        match (self.in_workspace, self.active) {
+            (true, _) => tr_with("window-title", &[("task", &tr("workspace-title"))]),
+            (false, Some(index)) => tr_with("window-title", &[("task", &REGISTRY[index].title())]),
+            (false, None) => tr("app-title"),
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Open(index) => self.open(index),
+            Message::OpenWorkspace => self.open_workspace(),
+            Message::Back => {
+                self.active = None;
+                self.in_workspace = false;
+            },
+            Message::SearchChanged(query) => self.menu.search(query),
+            Message::Key(key) => self.key(key),
+            Message::DismissError => {
+                self.error = None;
+                self.theme_error = None;
+            },
+            Message::ThemeSelected(preset) => {
+                self.select_theme(preset);
+                self.saved.theme = Some(preset.name().to_string());
+            },
+            Message::ScaleSelected(scale) => {
+                self.scale = scale;
+                self.saved.scale = Some(scale.percent());
+            },
+            Message::LanguageSelected(language) => self.select_language(language),
+            Message::TextSizeSelected(text_size) => {
+                self.text_size = text_size;
+                self.saved.text_size = Some(text_size.pixels());
+            },
+            Message::CheckThemeFile => {
+                if let Some(result) = self.theme_file.poll() {
+                    self.apply_theme_file(result)
+                }
+            },
+            // the size is given in scaled units, it is kept as the size of the window
+            Message::WindowResized(width, height) => {
+                let (width, height) = self.scale.window((width, height));
+                self.window = Size::new(width as f32, height as f32);
+                let key = match (self.standalone, self.active) {
+                    (true, Some(index)) => REGISTRY[index].name,
+                    _ => settings::LAUNCHER,
+                };
+                self.saved.windows.insert(key.to_string(), (width, height));
+            },
+            Message::Debug(m) => {
+                let task = match self.active {
+                    Some(index) => self.tasks[index].as_mut().map(|task| (index, task)),
+                    None => None,
+                };
+                self.debugger.update(m, task);
+            },
+            Message::Workspace(m) => {
+                for (pane, message) in self.workspace.update(m, &self.saved) {
+                    self.dispatch_pane(pane, message);
+                }
+            },
+            Message::Task(index, m) => self.dispatch(index, m),
+        }
+        Command::none()
+    }
+
+    // only the active task is subscribed to, a hidden timer catches up once it is reopened.
+    // A task paused by the debugger isn't subscribed to either
+    fn subscription(&self) -> Subscription<Message> {
+        let active = self.active.filter(|&index| !self.debugger.is_paused(index));
+
+        let task = match active.and_then(|index| Some((index, self.tasks[index].as_ref()?))) {
+            Some((index, task)) => Subscription::batch(vec![task.subscription(), task.shortcuts()])
+                .with(index)
+                .map(|(index, m)| Message::Task(index, m)),
+            None => Subscription::none(),
+        };
+
+        let theme_file = match self.theme {
+            Preset::Custom => time::every(THEME_POLL).map(|_| Message::CheckThemeFile),
+            _ => Subscription::none(),
+        };
+
+        let workspace = match self.in_workspace {
+            true => self.workspace.subscription().map(Message::Workspace),
+            false => Subscription::none(),
+        };
+
+        let window = iced_native::subscription::events_with(window_event);
+
+        Subscription::batch(vec![task, workspace, theme_file, window])
+    }
+
+    fn background_color(&self) -> Color {
+        self.theme().palette.background
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.scale.factor()
+    }
+
+    fn view(&mut self) -> Element<'_, Message> {
+        let theme = self.theme();
+        let size = self.content_size();
+        let active = match self.active {
+            Some(index) => self.tasks[index].as_mut().map(|task| (index, task)),
+            None => None,
+        };
+
+        let body = match (self.in_workspace, active) {
+            (true, _) => {
+                let content = self.workspace.view(theme, size).map(Message::Workspace);
+                nav(&mut self.back, &tr("workspace-title"), content, theme)
+            },
+            (false, Some((index, task))) => {
+                let state = task.state();
+                // the debugger takes the lower half of the window
+                let size = match self.debugger.is_open() {
+                    true => Size::new(size.width, size.height / 2.),
+                    false => size,
+                };
+                let content = task.view(&theme, size).map(move |m| Message::Task(index, m));
+
+                let content = match self.debugger.is_open() {
+                    true => Column::new()
+                        .push(Container::new(content).height(Length::FillPortion(1)))
+                        .push(Container::new(self.debugger.view(index, state, theme).map(Message::Debug))
+                            .height(Length::FillPortion(1)))
+                        .into(),
+                    false => content,
+                };
+
+                // a task launched directly has no navigation bar, but still shows errors above it
+                match self.standalone {
+                    true => content,
+                    false => nav(&mut self.back, &REGISTRY[index].title(), content, theme),
+                }
+            },
+            (false, None) => self.menu.view(self.theme, self.scale, self.text_size, theme),
+        };
+
+        let content = match self.error.as_ref().or(self.theme_error.as_ref()) {
+            Some(error) => Column::new()
+                .push(banner(&mut self.dismiss, error, theme))
+                .push(body)
+                .into(),
+            None => body,
+        };
+
+        page(content, theme)
+    }
+}
+
+impl Landing {
+    // a task's saved preferences are restored when it is first created
+    fn open(&mut self, index: usize) {
+        if self.tasks[index].is_none() {
+            let entry = &REGISTRY[index];
+            let task = (entry.create)();
+            let restored = task.restore(&self.saved.tasks.get(entry.name).cloned().unwrap_or_default());
+
+            self.debugger.track(index, task.as_ref());
+            self.tasks[index] = Some(task);
+            for message in restored {
+                self.dispatch(index, message);
+            }
+        }
+        self.active = Some(index);
+        // the full list is shown again on the way back to the menu
+        self.menu.search(String::new());
+    }
+
+    // the workspace's saved tasks are created when it is first shown, like a task opened from the menu
+    fn open_workspace(&mut self) {
+        self.in_workspace = true;
+        for (pane, message) in self.workspace.show(&self.saved) {
+            self.dispatch_pane(pane, message);
+        }
+    }
+
+    // the space left for a task or the workspace once the bars above it are shown, in scaled units.
+    // The error banner is about as tall as the navigation bar
+    fn content_size(&self) -> Size {
+        let bar = f32::from(nav_height(self.theme()));
+        let bars = match (self.standalone, self.error.is_some() || self.theme_error.is_some()) {
+            (true, false) => 0.,
+            (true, true) | (false, false) => bar,
+            (false, true) => bar * 2.,
+        };
+        let factor = self.scale.factor() as f32;
+
+        Size::new(self.window.width / factor, (self.window.height / factor - bars).max(0.))
+    }
+
+    // keys are only handled by the launcher, a task launched directly has no menu to navigate
+    fn key(&mut self, key: Key) {
+        if self.standalone {
+            return;
+        }
+
+        // the menu is shown when neither a task nor the workspace is open
+        let menu = self.active.is_none() && !self.in_workspace;
+
+        match (menu, key) {
+            (false, Key::Escape) => {
+                self.active = None;
+                self.in_workspace = false;
+            },
+            (false, _) => (),
+            (true, Key::Up) => self.menu.move_focus(-1),
+            (true, Key::Down) => self.menu.move_focus(1),
+            (true, Key::Enter) => {
+                if let Some(index) = self.menu.focused() {
+                    self.open(index)
+                }
+            },
+            (true, Key::Hotkey(index)) if index < REGISTRY.len() => self.open(index),
+            (true, Key::Type(c)) => self.menu.start_search(c),
+            (true, Key::Escape) => self.menu.search(String::new()),
+            (true, _) => (),
+        }
+    }
+
+    // every message reaching a launcher's task passes through here, and every one reaching a pane's
+    // task through 'dispatch_pane', so a recording misses nothing.
+    // Messages sent while the debugger has the task paused are dropped
+    fn dispatch(&mut self, index: usize, message: TaskMessage) {
+        let task = match &mut self.tasks[index] {
+            Some(task) if !self.debugger.is_paused(index) => task,
+            _ => return,
+        };
+        let message = task.prepare(message);
 
-    fn new() -> Self {Self::default()}
-    fn title(&self) -> String {"7Guis - Iced".to_string()}
+        // the message is described before it is handled, as it is recorded, a timer's tic
+        // amounts to nothing once the task has caught up with it
+        let result = match &mut self.recorder {
+            Some(recorder) => recorder.record(REGISTRY[index].name, None, task.as_ref(), &message),
+            None => Ok(()),
+        };
+        let label = task.describe(&message);
 
-    fn update(&mut self, message: Message) {
-         match message {
-            Message::Counter => counter::main(),
-            Message::Temperature => temperature::main(),
-            Message:: Flights => flights::main(),
-            Message::Timer => timer::main(),
-            Message::CRUD => crud::main(),
-            _ => Ok(())
-        }.unwrap_or(());
+        task.update(message);
+        self.debugger.handled(index, label, task.as_ref());
+        self.recorded(result);
     }
 
-    fn view(&mut self) -> Element<Message> {
+    // a pane's messages are recorded under its task's name and the pane's copy of the task, so each
+    // copy is replayed on its own. The debugger only follows the task open in the launcher, so it doesn't see them
+    fn dispatch_pane(&mut self, pane: pane_grid::Pane, message: TaskMessage) {
+        let (index, instance, task) = match self.workspace.task_mut(&pane) {
+            Some(task) => task,
+            None => return,
+        };
+        let message = task.prepare(message);
+
+        let result = match &mut self.recorder {
+            Some(recorder) => recorder.record(REGISTRY[index].name, Some(format!("pane-{}", instance)), task, &message),
+            None => Ok(()),
+        };
+
+        task.update(message);
+        self.recorded(result);
+    }
+
+    // a message which can't be written stops the recording, rather than leaving gaps in it
+    fn recorded(&mut self, result: Result<(), String>) {
+        if let Err(error) = result {
+            let error = format!("{}, the recording has been stopped", error);
+            eprintln!("7Guis: {}", error);
+            self.error = Some(error);
+            self.recorder = None;
+        }
+    }
+
+    // tasks are told of the switch through 'dispatch', so it is part of a recording
+    fn select_language(&mut self, language: Language) {
+        let previous = locale::current();
+        locale::set(language);
+        self.saved.language = Some(language.code().to_string());
+
+        for index in 0..self.tasks.len() {
+            let messages = match &self.tasks[index] {
+                Some(task) => task.language_changed(previous, language),
+                None => continue,
+            };
+            for message in messages {
+                self.dispatch(index, message);
+            }
+        }
+        for (pane, message) in self.workspace.language_changed(previous, language) {
+            self.dispatch_pane(pane, message);
+        }
+    }
+
+    // the custom theme falls back to the light theme until a valid theme file has been read
+    fn theme(&self) -> Theme {
+        let theme = self.theme.builtin().or(self.custom).unwrap_or(Theme::LIGHT);
+        Theme { text_size: self.text_size.pixels(), ..theme }
+    }
+
+    fn select_theme(&mut self, preset: Preset) {
+        self.theme = preset;
+        if preset == Preset::Custom {
+            let result = self.theme_file.load();
+            self.apply_theme_file(result);
+        }
+    }
+
+    // an invalid file keeps the last valid theme on screen and reports what is wrong with it
+    fn apply_theme_file(&mut self, result: Result<Theme, String>) {
+        match result {
+            Ok(theme) => {
+                self.custom = Some(theme);
+                self.theme_error = None;
+            },
+            Err(error) => {
+                eprintln!("7Guis: {}", error);
+                self.theme_error = Some(error);
+            },
+        }
+    }
+}
+
+// The settings are saved when the window is closed, tasks that were never opened keep
+// the preferences saved by earlier runs
+impl Drop for Landing {
+    fn drop(&mut self) {
+        for (entry, task) in REGISTRY.iter().zip(&self.tasks) {
+            if let Some(task) = task {
+                self.saved.tasks.insert(entry.name.to_string(), task.preferences());
+            }
+        }
+        if !self.standalone {
+            self.saved.last_task = match self.in_workspace {
+                true => Some(settings::WORKSPACE.to_string()),
+                false => self.active.map(|index| REGISTRY[index].name.to_string()),
+            };
+        }
+        self.saved.workspace = Some(self.workspace.layout());
+
+        if let Err(error) = self.saved.save() {
+            eprintln!("7Guis: {}", error);
+        }
+    }
+}
+
+// Window events the launcher reacts to. Keys taken by a focused widget, such as a text input,
+// are only used when they mean nothing to that widget
+fn window_event(event: iced_native::Event, status: iced_native::event::Status) -> Option<Message> {
+    use iced_native::{event::Status, keyboard, window, Event};
+
+    let ignored = status == Status::Ignored;
+
+    match event {
+        Event::Window(window::Event::Resized { width, height }) => Some(Message::WindowResized(width, height)),
+        Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => match key_code {
+            KeyCode::F12 => Some(Message::Debug(debugger::Message::Toggle)),
+            KeyCode::Up => Some(Message::Key(Key::Up)),
+            KeyCode::Down => Some(Message::Key(Key::Down)),
+            KeyCode::Enter if ignored => Some(Message::Key(Key::Enter)),
+            KeyCode::Escape if ignored => Some(Message::Key(Key::Escape)),
+            _ if ignored => hotkey(key_code).map(|index| Message::Key(Key::Hotkey(index))),
+            _ => None,
+        },
+        // digits are hotkeys, they only reach the search field once it has been focused
+        Event::Keyboard(keyboard::Event::CharacterReceived(c))
+            if ignored && !c.is_control() && !c.is_whitespace() && !c.is_ascii_digit() => {
+            Some(Message::Key(Key::Type(c)))
+        },
+        _ => None,
+    }
+}
+
+// the registry index opened by a number key, 1 being the first task
+fn hotkey(key_code: KeyCode) -> Option<usize> {
+    const ROW: [KeyCode; 9] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5,
+                               KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9];
+    const NUMPAD: [KeyCode; 9] = [KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+                                  KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8,
+                                  KeyCode::Numpad9];
+
+    ROW.iter().position(|&key| key == key_code)
+        .or_else(|| NUMPAD.iter().position(|&key| key == key_code))
+}
+
+// A button for each registered task matching the search, and the theme selection.
+// The arrow keys move a focus through the buttons which Enter opens
+struct Menu {
+    cards: Vec<button::State>,
+    workspace: button::State,
+    thumbnails: Vec<image::Handle>,
+    scroll: scrollable::State,
+    theme: pick_list::State<Preset>,
+    scale: pick_list::State<Scale>,
+    text_size: pick_list::State<TextSize>,
+    language: pick_list::State<Language>,
+    search: text_input::State,
+    query: String,
+    // the position of the focused button among those matching the search
+    focus: usize,
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        Menu {
+            cards: REGISTRY.iter().map(|_| button::State::default()).collect(),
+            workspace: button::State::default(),
+            thumbnails: REGISTRY.iter().map(|entry| image::Handle::from_memory(entry.thumbnail.to_vec())).collect(),
+            scroll: scrollable::State::default(),
+            theme: pick_list::State::default(),
+            scale: pick_list::State::default(),
+            text_size: pick_list::State::default(),
+            language: pick_list::State::default(),
+            search: text_input::State::default(),
+            query: String::new(),
+            focus: 0,
+        }
+    }
+}
+
+impl Menu {
+    // the registry indices of the tasks whose name, title or description contain the query
+    fn matches(&self) -> Vec<usize> {
+        let query = self.query.to_lowercase();
+
+        REGISTRY.iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                [entry.name.to_string(), entry.title(), entry.description()].iter()
+                    .any(|s| s.to_lowercase().contains(&query))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn focused(&self) -> Option<usize> {
+        self.matches().get(self.focus).copied()
+    }
+
+    fn move_focus(&mut self, by: isize) {
+        let last = self.matches().len().saturating_sub(1) as isize;
+        self.focus = (self.focus as isize + by).max(0).min(last) as usize;
+    }
+
+    fn search(&mut self, query: String) {
+        self.query = query;
+        self.focus = 0;
+    }
+
+    // typing anywhere in the menu moves into the search field
+    fn start_search(&mut self, c: char) {
+        self.search.focus();
+        self.search.move_cursor_to_end();
+        let mut query = std::mem::take(&mut self.query);
+        query.push(c);
+        self.search(query);
+    }
+
+    fn view(&mut self, preset: Preset, scale: Scale, text_size: TextSize, theme: Theme) -> Element<'_, Message> {
+        let matches = self.matches();
+        let focused = matches.get(self.focus).copied();
+
+        let title = Text::new(tr("menu-heading"))
+            .width(Length::Fill)
+            .size(theme.text(30))
+            .color(theme.palette.primary)
+            .horizontal_alignment(HorizontalAlignment::Center);
+
+        let theme_list = PickList::new(&mut self.theme, &Preset::ALL[..], Some(preset), Message::ThemeSelected)
+            .width(Length::Fill)
+            .text_size(theme.text_size)
+            .style(theme::PickList(theme));
+
+        let scale_list = PickList::new(&mut self.scale, &Scale::STEPS[..], Some(scale), Message::ScaleSelected)
+            .width(Length::Fill)
+            .text_size(theme.text_size)
+            .style(theme::PickList(theme));
+
+        let language_list = PickList::new(&mut self.language, &Language::ALL[..], Some(locale::current()),
+                                          Message::LanguageSelected)
+            .width(Length::Fill)
+            .text_size(theme.text_size)
+            .style(theme::PickList(theme));
+
+        let text_size_list = PickList::new(&mut self.text_size, &TextSize::STEPS[..], Some(text_size),
+                                           Message::TextSizeSelected)
+            .width(Length::Fill)
+            .text_size(theme.text_size)
+            .style(theme::PickList(theme));
+
+        let search = TextInput::new(&mut self.search, &tr("menu-search"), &self.query, Message::SearchChanged)
+            .padding(5)
+            .size(theme.text_size)
+            .on_submit(Message::Key(Key::Enter))
+            .style(theme::TextInput(theme, theme::Input::Enabled));
+
+        // the number shown on each card is its hotkey, which stays the same while searching
+        let cards = REGISTRY.iter()
+            .zip(self.cards.iter_mut().zip(&self.thumbnails))
+            .enumerate()
+            .filter(|(index, _)| matches.contains(index))
+            .fold(Scrollable::new(&mut self.scroll).spacing(5), |list, (index, (entry, (state, thumbnail)))| {
+                let style: Box<dyn button::StyleSheet> = match focused == Some(index) {
+                    true => Box::new(theme::FocusedButton(theme)),
+                    false => Box::new(theme::Button(theme)),
+                };
+                let thumbnail = Image::new(thumbnail.clone()).width(Length::Units(THUMBNAIL_WIDTH));
+                let title = format!("{}  {}", index + 1, entry.title());
+
+                list.push(Button::new(state, card(thumbnail.into(), title, &entry.description(), entry.status, theme))
+                    .width(Length::Fill)
+                    .style(style)
+                    .on_press(Message::Open(index)))
+            });
+
+        let query = self.query.to_lowercase();
+        let cards = PLANNED.iter()
+            .filter(|planned| planned.title().to_lowercase().contains(&query)
+                || planned.description().to_lowercase().contains(&query))
+            .fold(cards, |list, planned| {
+                let placeholder = Space::new(Length::Units(THUMBNAIL_WIDTH), Length::Shrink);
+
+                list.push(Container::new(card(placeholder.into(), planned.title(), &planned.description(),
+                                              Status::NotStarted, theme))
+                    .padding(PAD)
+                    .width(Length::Fill)
+                    .style(theme::Panel(theme)))
+            })
+            .height(Length::Fill)
+            .style(theme::Scrollable(theme));
+
         Column::new()
             .padding(10)
             .spacing(5)
-            .push(Text::new("7GUIs\nfor\nIced")
-                .width(Length::Fill)
-                .size(40)
-                .color([0.1, 0.1, 0.6])
-                .horizontal_alignment(HorizontalAlignment::Center)
-            )
-            .push(
-                Button::new(&mut self.counter, Text::new("Counter")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill).on_press(Message::Counter))
-            .push(
-                Button::new(&mut self.temperature, Text::new("Temperature")
+            .push(title)
+            .push(search)
+            .push(Button::new(&mut self.workspace, Text::new(tr("menu-workspace"))
+                    .size(theme.text_size)
                     .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill).on_press(Message::Temperature))
-            .push(
-                Button::new(&mut self.flights, Text::new("Flights")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill).on_press(Message::Flights))
-            .push(
-                Button::new(&mut self.timer, Text::new("Timer")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill).on_press(Message::Timer))
-            .push(
-                Button::new(&mut self.crud, Text::new("CRUD")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill).on_press(Message::CRUD))
-            .push(
-                Button::new(&mut self.circle, Text::new("Circles")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill))
-            .push(
-                Button::new(&mut self.cells, Text::new("Cells")
-                    .horizontal_alignment(HorizontalAlignment::Center)
-                    .width(Length::Fill)).width(Length::Fill))
+                    .width(Length::Fill))
+                .width(Length::Fill)
+                .style(theme::Button(theme))
+                .on_press(Message::OpenWorkspace))
+            .push(cards)
+            .push(Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(tr("menu-theme")).size(theme.text_size))
+                .push(theme_list)
+                .push(Text::new(tr("menu-language")).size(theme.text_size))
+                .push(language_list))
+            .push(Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Text::new(tr("menu-scale")).size(theme.text_size))
+                .push(scale_list)
+                .push(Text::new(tr("menu-text-size")).size(theme.text_size))
+                .push(text_size_list))
             .into()
     }
-}
\ No newline at end of file
+}
+
+// A task's thumbnail beside its title, description and how much of it is done
+fn card<'a>(thumbnail: Element<'a, Message>, title: String, description: &str, status: Status, theme: Theme)
+    -> Element<'a, Message> {
+    let status_color = match status {
+        Status::Implemented => theme.palette.primary,
+        Status::Partial(_) => theme.palette.invalid,
+        Status::NotStarted => theme.palette.text_muted,
+    };
+
+    Row::new()
+        .spacing(10)
+        .align_items(Align::Center)
+        .push(thumbnail)
+        .push(Column::new()
+            .spacing(3)
+            .width(Length::Fill)
+            .push(Text::new(title).size(theme.text(20)))
+            .push(Text::new(description).size(theme.text(14)))
+            .push(Text::new(status.to_string()).size(theme.text(14)).color(status_color)))
+        .into()
+}
+
+// the navigation bar grows with the text in it, but is never shorter than usual
+fn nav_height(theme: Theme) -> u16 {
+    NAV_HEIGHT.max(theme.text(NAV_HEIGHT))
+}
+
+// Puts a navigation bar back to the menu above what is open
+fn nav<'a>(back: &'a mut button::State, title: &str, content: Element<'a, Message>, theme: Theme)
+    -> Element<'a, Message> {
+    let bar = Row::new()
+        .padding(5)
+        .spacing(10)
+        .align_items(Align::Center)
+        .height(Length::Units(nav_height(theme)))
+        .push(Button::new(back, Text::new(tr("nav-back")).size(theme.text_size))
+            .style(theme::Button(theme))
+            .on_press(Message::Back))
+        .push(Text::new(title).size(theme.text_size));
+
+    Column::new()
+        .push(bar)
+        .push(content)
+        .into()
+}
+
+// Fills the window with the theme's background and text colour
+fn page(content: Element<'_, Message>, theme: Theme) -> Element<'_, Message> {
+    Container::new(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .style(theme::Page(theme))
+        .into()
+}
+
+// A dismissible row reporting an error
+fn banner<'a>(dismiss: &'a mut button::State, error: &str, theme: Theme) -> Element<'a, Message> {
+    Row::new()
+        .padding(5)
+        .spacing(10)
+        .align_items(Align::Center)
+        .push(Text::new(error).size(theme.text_size).color(theme.palette.invalid).width(Length::Fill))
+        .push(Button::new(dismiss, Text::new(tr("banner-dismiss")).size(theme.text_size))
+            .style(theme::Button(theme))
+            .on_press(Message::DismissError))
+        .into()
+}
@@ -0,0 +1,52 @@
+// # Recording
+// Writes every message handled by a task to a JSON Lines file, which '--replay' feeds back
+// into the models without opening a window. Each line is flushed as it is written so a
+// recording survives a crash
+
+use sevenguis_model::replay::Record;
+use crate::task::{DynTask, TaskMessage};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+pub struct Recorder {
+    path: PathBuf,
+    file: LineWriter<File>,
+    started: Instant,
+}
+
+impl Recorder {
+    // an existing recording at the path is replaced
+    pub fn create(path: &Path) -> Result<Recorder, String> {
+        let file = File::create(path).map_err(|e| format!("{} could not be created: {}", path.display(), e))?;
+
+        Ok(Recorder {
+            path: path.to_path_buf(),
+            file: LineWriter::new(file),
+            started: Instant::now(),
+        })
+    }
+
+    // the message a task is about to handle, when it is one for the task's model. 'instance' tells
+    // apart the copies of a task in the workspace's panes, it is None for the launcher's own
+    pub fn record(&mut self, name: &str, instance: Option<String>, task: &dyn DynTask, message: &TaskMessage)
+        -> Result<(), String> {
+        match task.record(message) {
+            Some(value) => value.and_then(|value| self.write(name, instance, value)),
+            None => Ok(()),
+        }
+    }
+
+    fn write(&mut self, task: &str, instance: Option<String>, message: serde_json::Value) -> Result<(), String> {
+        let record = Record {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            task: task.to_string(),
+            instance,
+            message,
+        };
+        let line = serde_json::to_string(&record).map_err(|e| format!("a message could not be recorded: {}", e))?;
+
+        writeln!(self.file, "{}", line).map_err(|e| format!("{} could not be written: {}", self.path.display(), e))
+    }
+}
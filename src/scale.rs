@@ -0,0 +1,81 @@
+// # Interface scale
+// How large the interface is drawn, for high-DPI screens and for anyone who finds the default too
+// small. The scale enlarges everything, padding and widget widths included, while the text size
+// changes only the text. Both are chosen in the launcher or on the command line
+
+use std::fmt;
+
+use crate::theme::DEFAULT_TEXT_SIZE;
+
+// As a percentage of the size the layouts were designed at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scale(u16);
+
+impl Scale {
+    pub const MIN: u16 = 75;
+    pub const MAX: u16 = 300;
+    // offered in the launcher, any percentage in range can be given on the command line
+    pub const STEPS: [Scale; 9] = [Scale(75), Scale(90), Scale(100), Scale(110), Scale(125), Scale(150),
+                                   Scale(200), Scale(250), Scale(300)];
+
+    pub fn new(percent: u16) -> Option<Scale> {
+        (Scale::MIN..=Scale::MAX).contains(&percent).then_some(Scale(percent))
+    }
+
+    pub fn percent(self) -> u16 {
+        self.0
+    }
+
+    pub fn factor(self) -> f64 {
+        f64::from(self.0) / 100.
+    }
+
+    // a size given by a task in unscaled units, as the window needs to be to fit it
+    pub fn window(self, (width, height): (u32, u32)) -> (u32, u32) {
+        let scale = |length: u32| (f64::from(length) * self.factor()).round() as u32;
+        (scale(width), scale(height))
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Scale(100)
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+// The size of body text in pixels before scaling, other text keeps its proportion to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextSize(u16);
+
+impl TextSize {
+    pub const MIN: u16 = 10;
+    pub const MAX: u16 = 40;
+    pub const STEPS: [TextSize; 9] = [TextSize(12), TextSize(14), TextSize(16), TextSize(18), TextSize(20),
+                                      TextSize(24), TextSize(28), TextSize(32), TextSize(40)];
+
+    pub fn new(pixels: u16) -> Option<TextSize> {
+        (TextSize::MIN..=TextSize::MAX).contains(&pixels).then_some(TextSize(pixels))
+    }
+
+    pub fn pixels(self) -> u16 {
+        self.0
+    }
+}
+
+impl Default for TextSize {
+    fn default() -> Self {
+        TextSize(DEFAULT_TEXT_SIZE)
+    }
+}
+
+impl fmt::Display for TextSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} px", self.0)
+    }
+}
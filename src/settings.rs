@@ -0,0 +1,58 @@
+// # Settings
+// What the launcher remembers between runs, kept in 'settings.toml' in the config directory.
+// The file is read when the application starts and written when it exits
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::config;
+use crate::task::Preferences;
+use crate::workspace::Layout;
+
+const FILE_NAME: &str = "settings.toml";
+// the key window sizes of the launcher are stored under, tasks use their own names
+pub const LAUNCHER: &str = "launcher";
+// saved as the last task when the workspace was open
+pub const WORKSPACE: &str = "workspace";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // the task that was open when the launcher was closed
+    pub last_task: Option<String>,
+    pub theme: Option<String>,
+    // the interface scale as a percentage, and the size of body text in pixels
+    pub scale: Option<u16>,
+    pub text_size: Option<u16>,
+    // the code of the language, e.g. "de"
+    pub language: Option<String>,
+    // window sizes as [width, height]
+    pub windows: BTreeMap<String, (u32, u32)>,
+    // preferences of each task, under the task's name
+    pub tasks: BTreeMap<String, Preferences>,
+    // the panes of the workspace and the task in each
+    pub workspace: Option<Layout>,
+}
+
+impl Settings {
+    // A missing file is not an error, it only means nothing has been saved yet
+    pub fn load() -> Result<Settings, String> {
+        let path = match config::path(FILE_NAME) {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Settings::default()),
+        };
+
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("{} could not be read: {}", path.display(), e))?;
+
+        toml::from_str(&text).map_err(|e| format!("{} is not valid, the defaults are used instead: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = config::path(FILE_NAME).ok_or_else(|| "no config directory could be found for the settings".to_string())?;
+        let text = toml::to_string_pretty(self).map_err(|e| format!("the settings could not be written: {}", e))?;
+
+        // written alongside and renamed, a crash mid-write would otherwise lose every setting
+        config::write_atomic(&path, &text)
+    }
+}
@@ -0,0 +1,297 @@
+// # Tasks
+// The interface each GUI implements to be hosted by the launcher, and the registry the menu is built from
+
+use iced::{window, Element, Size, Subscription};
+use sevenguis_model::Model;
+use std::any::Any;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::{counter, crud, flights, locale, temperature, timer};
+use crate::locale::Language;
+use crate::theme::Theme;
+
+// Every task that can be opened, in the order they are listed in the menu
+pub const REGISTRY: &[Entry] = &[
+    Entry::of::<counter::Counter>(),
+    Entry::of::<temperature::Temperature>(),
+    Entry::of::<flights::Flights>(),
+    Entry::of::<timer::Timer>(),
+    Entry::of::<crud::Crud>(),
+];
+
+// The 7GUIs tasks which haven't been started, listed in the menu so the whole set is shown
+pub const PLANNED: &[Planned] = &[
+    Planned { name: "circles" },
+    Planned { name: "cells" },
+];
+
+// Preferences a task keeps between runs, saved in the settings file under the task's name
+pub type Preferences = toml::value::Table;
+
+// Looks up a registered task by the name used on the command line
+pub fn find(name: &str) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.name.eq_ignore_ascii_case(name))
+}
+
+// The position of a registered task in the registry, by its name
+pub fn index(name: &str) -> Option<usize> {
+    REGISTRY.iter().position(|entry| entry.name.eq_ignore_ascii_case(name))
+}
+
+
+// ## The trait implemented by each GUI
+pub trait GuiTask: 'static {
+    type Message: Debug + Clone + Send + Sync + 'static;
+    // the model shown by the task, its name is used to select the task from the command line.
+    // The task's title and a one line summary of what it demonstrates are the messages
+    // '<name>-title' and '<name>-description' in the message files
+    type Model: Model;
+
+    const STATUS: Status;
+    // a screenshot of the task, shown on its card in the menu
+    const THUMBNAIL: &'static [u8];
+
+    // the window given to the task when it is launched directly
+    fn window() -> window::Settings;
+
+    fn new() -> Self;
+
+    // the message as it is to be handled, worked out once before it is recorded. A message needing
+    // something from outside the task, such as a file, is turned into one carrying it here,
+    // so what is recorded is what gets applied
+    fn prepare(&self, message: Self::Message) -> Self::Message {
+        message
+    }
+
+    // the model message a message from the view amounts to, this is what gets recorded.
+    // None for a message which leaves the model alone, it is neither recorded nor kept by the debugger
+    fn model_message(&self, message: &Self::Message) -> Option<<Self::Model as Model>::Message>;
+
+    fn model(&self) -> &Self::Model;
+
+    fn model_mut(&mut self) -> &mut Self::Model;
+
+    // replaces the model with an earlier state, when the debugger rewinds or resumes the task
+    fn rewind(&mut self, model: Self::Model) {
+        *self.model_mut() = model;
+    }
+
+    fn update(&mut self, message: Self::Message);
+
+    // the theme is chosen in the launcher, every widget should be styled from it.
+    // 'size' is the space the task is shown in, which its layout should fit without clipping
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Self::Message>;
+
+    // produces messages for update to handle as long as the task is open
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::none()
+    }
+
+    // keyboard shortcuts, only listened to while the task has the keyboard: it is open in the launcher,
+    // or its pane in the workspace was the last one clicked
+    fn shortcuts(&self) -> Subscription<Self::Message> {
+        Subscription::none()
+    }
+
+    // the messages applying preferences saved by an earlier run, sent once when the task is created.
+    // The preferences are empty when nothing has been saved for the task yet
+    fn restore(&self, _preferences: &Preferences) -> Vec<Self::Message> {
+        Vec::new()
+    }
+
+    // the preferences to save when the application exits
+    fn preferences(&self) -> Preferences {
+        Preferences::new()
+    }
+
+    // the messages to send once the language has been switched, for what the language decides
+    // beyond the text, such as the order of dates
+    fn language_changed(&self, _previous: Language, _language: Language) -> Vec<Self::Message> {
+        Vec::new()
+    }
+}
+
+
+// ## Type erasure
+// The launcher holds tasks with different message types, so their messages are boxed up
+// and only unwrapped again by the task that produced them
+
+#[derive(Debug, Clone)]
+pub struct TaskMessage(Arc<dyn AnyMessage>);
+
+impl TaskMessage {
+    fn new<M: AnyMessage>(message: M) -> Self {
+        TaskMessage(Arc::new(message))
+    }
+
+    fn downcast<M: Clone + 'static>(&self) -> Option<M> {
+        self.0.as_any().downcast_ref::<M>().cloned()
+    }
+}
+
+trait AnyMessage: Debug + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Debug + Send + Sync + 'static> AnyMessage for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+// The object safe counterpart of 'GuiTask', implemented for every task
+pub trait DynTask {
+    fn prepare(&self, message: TaskMessage) -> TaskMessage;
+    fn update(&mut self, message: TaskMessage);
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, TaskMessage>;
+    fn subscription(&self) -> Subscription<TaskMessage>;
+    fn shortcuts(&self) -> Subscription<TaskMessage>;
+    fn restore(&self, preferences: &Preferences) -> Vec<TaskMessage>;
+    fn preferences(&self) -> Preferences;
+    fn language_changed(&self, previous: Language, language: Language) -> Vec<TaskMessage>;
+    // the model message to record, None when the message belongs to another task or isn't for the model
+    fn record(&self, message: &TaskMessage) -> Option<Result<serde_json::Value, String>>;
+    // the model message as it is listed in the debugger's history
+    fn describe(&self, message: &TaskMessage) -> Option<String>;
+    fn snapshot(&self) -> Snapshot;
+    fn rewind(&mut self, snapshot: &Snapshot);
+    // the model as a tree of values, for the debugger to display
+    fn state(&self) -> Result<serde_json::Value, String>;
+}
+
+// A copy of a task's model, only understood by the task it was taken from
+pub struct Snapshot(Box<dyn Any>);
+
+impl<T: GuiTask> DynTask for T {
+    fn prepare(&self, message: TaskMessage) -> TaskMessage {
+        match message.downcast::<T::Message>() {
+            Some(message) => TaskMessage::new(GuiTask::prepare(self, message)),
+            None => message,
+        }
+    }
+
+    fn update(&mut self, message: TaskMessage) {
+        if let Some(message) = message.downcast::<T::Message>() {
+            GuiTask::update(self, message)
+        }
+    }
+
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, TaskMessage> {
+        GuiTask::view(self, theme, size).map(TaskMessage::new)
+    }
+
+    fn subscription(&self) -> Subscription<TaskMessage> {
+        GuiTask::subscription(self).map(TaskMessage::new)
+    }
+
+    fn shortcuts(&self) -> Subscription<TaskMessage> {
+        GuiTask::shortcuts(self).map(TaskMessage::new)
+    }
+
+    fn restore(&self, preferences: &Preferences) -> Vec<TaskMessage> {
+        GuiTask::restore(self, preferences).into_iter().map(TaskMessage::new).collect()
+    }
+
+    fn preferences(&self) -> Preferences {
+        GuiTask::preferences(self)
+    }
+
+    fn language_changed(&self, previous: Language, language: Language) -> Vec<TaskMessage> {
+        GuiTask::language_changed(self, previous, language).into_iter().map(TaskMessage::new).collect()
+    }
+
+    fn record(&self, message: &TaskMessage) -> Option<Result<serde_json::Value, String>> {
+        let message = self.model_message(&message.downcast::<T::Message>()?)?;
+        Some(serde_json::to_value(message).map_err(|e| format!("a {} message could not be recorded: {}", T::Model::NAME, e)))
+    }
+
+    fn describe(&self, message: &TaskMessage) -> Option<String> {
+        Some(format!("{:?}", self.model_message(&message.downcast::<T::Message>()?)?))
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot(Box::new(self.model().clone()))
+    }
+
+    fn rewind(&mut self, snapshot: &Snapshot) {
+        if let Some(model) = snapshot.0.downcast_ref::<T::Model>() {
+            GuiTask::rewind(self, model.clone())
+        }
+    }
+
+    fn state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self.model()).map_err(|e| format!("the {} model could not be shown: {}", T::Model::NAME, e))
+    }
+}
+
+
+// ## Registry entries
+// How much of the 7GUIs specification a task covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Implemented,
+    // the id of a message saying what is still missing or differs from the specification
+    Partial(&'static str),
+    NotStarted,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Implemented => write!(f, "{}", locale::tr("status-implemented")),
+            Status::Partial(missing) => write!(f, "{}", locale::tr_with("status-partial", &[("missing", &locale::tr(missing))])),
+            Status::NotStarted => write!(f, "{}", locale::tr("status-not-started")),
+        }
+    }
+}
+
+// Describes a task without creating it
+#[derive(Debug)]
+pub struct Entry {
+    pub name: &'static str,
+    pub status: Status,
+    pub thumbnail: &'static [u8],
+    pub window: fn() -> window::Settings,
+    pub create: fn() -> Box<dyn DynTask>,
+}
+
+impl Entry {
+    pub const fn of<T: GuiTask>() -> Self {
+        Entry {
+            name: T::Model::NAME,
+            status: T::STATUS,
+            thumbnail: T::THUMBNAIL,
+            window: T::window,
+            create: create::<T>,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        locale::tr(&format!("{}-title", self.name))
+    }
+
+    pub fn description(&self) -> String {
+        locale::tr(&format!("{}-description", self.name))
+    }
+}
+
+fn create<T: GuiTask>() -> Box<dyn DynTask> {
+    Box::new(T::new())
+}
+
+// A task in the 7GUIs set without an implementation yet, its name is that of its messages
+#[derive(Debug)]
+pub struct Planned {
+    pub name: &'static str,
+}
+
+impl Planned {
+    pub fn title(&self) -> String {
+        locale::tr(&format!("{}-title", self.name))
+    }
+
+    pub fn description(&self) -> String {
+        locale::tr(&format!("{}-description", self.name))
+    }
+}
@@ -1,107 +1,267 @@
-use iced::{Align, Row, Element, Sandbox, Settings, Text, HorizontalAlignment, Length, TextInput, Space};
-use iced::{text_input};
+use iced::{Align, Checkbox, Column, Container, Row, Element, Text, HorizontalAlignment, Length, PickList, Scrollable,
+           TextInput, Size};
+use iced::{pick_list, scrollable, text_input, window};
+use sevenguis_model::temperature::{self, Invalid, Precision, Problem, Scale};
+use crate::locale::{self, tr, tr_with};
+use crate::task::{GuiTask, Preferences, Status};
+use crate::theme::{self, Input, Theme};
 
 // the window size is an unsigned 32 bit integer, the padding is unsigned 16 bit integer
-const SIZE: (u32, u32) = (300, 80);
+const DEFAULT_SIZE: (u32, u32) = (420, 240);
+const MIN_SIZE: (u32, u32) = (260, 240);
 const PAD: u16 = 25;
+const SPACING: u16 = 10;
+// below this width the fields are stacked rather than side by side
+const NARROW: f32 = 380.;
+const PAD_NARROW: u16 = 10;
+// the unit after each field, wide enough for the longest symbol
+const SYMBOL_WIDTH: u16 = 45;
+// the note under an invalid field is smaller than the field's text
+const TOOLTIP_SIZE: u16 = 15;
+// the scales of the 7GUIs task, shown until others are chosen
+const DEFAULT_SCALES: [Scale; 2] = [Scale::Celsius, Scale::Fahrenheit];
 
-pub fn main() -> iced::Result{
-    // Set the window properties
-    let mut settings = Settings::default();
-    settings.window.size = SIZE;
-    settings.window.resizable = false;
-
-    Temperature::run(settings)
-}
-
-#[derive(Default)]
-struct Temperature{
-    c_state: text_input::State,
-    f_state: text_input::State,
-    c_value: String,
-    f_value: String,
+pub struct Temperature{
+    model: temperature::Temperature,
+    inputs: [text_input::State; Scale::ALL.len()],
+    // the scales with a field, in the order of 'Scale::ALL'. There is always at least one
+    shown: Vec<Scale>,
+    precision: pick_list::State<Digits>,
+    scroll: scrollable::State,
 }
 
-
 #[derive(Debug, Clone)]
-enum Message{
-    CUpdated(String),
-    FUpdated(String),
+pub enum Message {
+    Model(temperature::Message),
+    Show(Scale, bool),
 }
 
-impl Sandbox for Temperature {
+impl GuiTask for Temperature {
     type Message = Message;
+    type Model = temperature::Temperature;
+
+    const STATUS: Status = Status::Implemented;
+    const THUMBNAIL: &'static [u8] = include_bytes!("../images/Temp.PNG");
+
+    fn window() -> window::Settings {
+        window::Settings {
+            size: DEFAULT_SIZE,
+            min_size: Some(MIN_SIZE),
+            ..window::Settings::default()
+        }
+    }
 
     fn new() -> Self {
-        Self::default()
+        Temperature {
+            model: temperature::Temperature::default(),
+            inputs: Default::default(),
+            shown: DEFAULT_SCALES.to_vec(),
+            precision: pick_list::State::default(),
+            scroll: scrollable::State::default(),
+        }
+    }
+
+    // the model converts into every scale, which are shown is up to the view
+    fn model_message(&self, message: &Message) -> Option<temperature::Message> {
+        match message {
+            Message::Model(message) => Some(message.clone()),
+            Message::Show(..) => None,
+        }
+    }
+
+    fn model(&self) -> &Self::Model {
+        &self.model
     }
 
-    fn title(&self) -> String {
-        String::from("Temperature Converter")
+    fn model_mut(&mut self) -> &mut Self::Model {
+        &mut self.model
     }
 
     fn update(&mut self, message:Message) {
         match message {
-            Message::CUpdated(s) => {
-                self.f_value = c_to_f(s.clone());
-                self.c_value = s;
+            Message::Model(message) => self.model.update(message),
+            Message::Show(scale, true) => {
+                if !self.shown.contains(&scale) {
+                    self.shown.push(scale);
+                    self.shown.sort();
+                }
             },
-            Message::FUpdated(s) => {
-                self.c_value = f_to_c(s.clone());
-                self.f_value = s;
+            // the last field can't be hidden, there would be nothing left to type in
+            Message::Show(scale, false) => {
+                if self.shown.len() > 1 {
+                    self.shown.retain(|&shown| shown != scale);
+                }
             },
         }
     }
 
-    fn view(&mut self) -> Element<Message> {
-        let c_in = TextInput::new(
-            &mut self.c_state,
-            "",
-            &self.c_value,
-                Message::CUpdated
-        )
-            .padding(5);
-
-        let f_in = TextInput::new(
-            &mut self.f_state,
-            "",
-            &self.f_value,
-            Message::FUpdated
-        )
-            .padding(5);
-
-        Row::new()
-            .padding(PAD)
+    // scales which aren't known are skipped, and a list without any known scale is ignored.
+    // The precision is either a number of 'decimals' or of 'significant' figures
+    fn restore(&self, preferences: &Preferences) -> Vec<Message> {
+        let mut messages = Vec::new();
+
+        let digits = |key: &str| preferences.get(key).and_then(|v| v.as_integer()).map(|digits| digits.clamp(0, 255) as u8);
+        let precision = digits("decimals").map(Precision::Decimals).or_else(|| digits("significant").map(Precision::Significant));
+        if let Some(precision) = precision {
+            messages.push(Message::Model(temperature::Message::PrecisionSelected(precision)));
+        }
+
+        let saved: Vec<Scale> = match preferences.get("scales").and_then(|v| v.as_array()) {
+            Some(names) => names.iter().filter_map(|name| Scale::from_name(name.as_str()?)).collect(),
+            None => Vec::new(),
+        };
+        if !saved.is_empty() {
+            // the saved scales are shown before any others are hidden, so one is always left
+            let show = saved.iter().map(|&scale| Message::Show(scale, true));
+            let hide = Scale::ALL.iter().filter(|scale| !saved.contains(scale)).map(|&scale| Message::Show(scale, false));
+            messages.extend(show.chain(hide));
+        }
+        messages
+    }
+
+    fn preferences(&self) -> Preferences {
+        let names = self.shown.iter().map(|scale| scale.name().into()).collect::<Vec<toml::Value>>();
+        let mut preferences = Preferences::new();
+        preferences.insert("scales".to_string(), names.into());
+        match self.model.precision() {
+            Precision::Decimals(decimals) => preferences.insert("decimals".to_string(), i64::from(decimals).into()),
+            Precision::Significant(figures) => preferences.insert("significant".to_string(), i64::from(figures).into()),
+        };
+        preferences
+    }
+
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Message> {
+        let narrow = size.width < NARROW;
+        let model = &self.model;
+        let shown = &self.shown;
+
+        // the model keeps numbers with a '.', they are shown and typed the language's way.
+        // The field being typed in shows exactly what was typed, the others are grouped into thousands
+        let fields = self.inputs.iter_mut()
+            .zip(Scale::ALL.iter().copied())
+            .filter(|(_, scale)| shown.contains(scale))
+            .map(|(state, scale)| {
+                let text = match model.typed(scale) {
+                    Some(typed) => locale::typed_number(typed),
+                    None => locale::number(&model.text(scale)),
+                };
+                let input = TextInput::new(
+                    state,
+                    "",
+                    &text,
+                    move |s| Message::Model(temperature::Message::Updated(scale, locale::parse_number(&s)))
+                )
+                    .padding(5)
+                    .size(theme.text_size)
+                    .style(theme::TextInput(*theme, if model.invalid(scale).is_some() { Input::Invalid } else { Input::Enabled }));
+
+                let symbol = Text::new(format!(" {}", scale.symbol()))
+                    .size(theme.text_size)
+                    .width(Length::Units(theme.text(SYMBOL_WIDTH)))
+                    .horizontal_alignment(HorizontalAlignment::Left);
+
+                let field = Row::new().align_items(Align::Center).push(input).push(symbol);
+                // an invalid field says what is wrong with it just below
+                let tooltip = model.invalid(scale).map(|invalid| {
+                    Container::new(Text::new(problem(scale, invalid)).size(theme.text(TOOLTIP_SIZE)))
+                        .padding(4)
+                        .style(theme::Tooltip(*theme))
+                });
+
+                let column = Column::new().width(Length::Fill).spacing(2).push(field);
+                match tooltip {
+                    Some(tooltip) => column.push(tooltip).into(),
+                    None => column.into(),
+                }
+            })
+            .collect();
+
+        let choices = Scale::ALL.iter()
+            .map(|&scale| {
+                Checkbox::new(shown.contains(&scale), tr(&format!("temperature-{}", scale.name())),
+                              move |show| Message::Show(scale, show))
+                    .text_size(theme.text_size)
+                    .width(Length::Fill)
+                    .style(theme::Checkbox(*theme))
+                    .into()
+            })
+            .collect();
+
+        // the fields other than the one being typed in are written to this precision
+        let precision = Row::new()
+            .spacing(SPACING)
             .align_items(Align::Center)
-            .push(c_in)
-            .push(
-                Text::new(" C".to_string())
-                    .horizontal_alignment(HorizontalAlignment::Left),
-            )
-            .push(Space::with_width(Length::Fill))
-            .push(f_in)
-            .push(
-                Text::new(" F".to_string())
-                    .horizontal_alignment(HorizontalAlignment::Left),
+            .push(Text::new(tr("temperature-precision")).size(theme.text_size))
+            .push(PickList::new(
+                &mut self.precision,
+                Precision::all().into_iter().map(Digits).collect::<Vec<_>>(),
+                Some(Digits(model.precision())),
+                |digits| Message::Model(temperature::Message::PrecisionSelected(digits.0)),
             )
+                .text_size(theme.text_size)
+                .style(theme::PickList(*theme)));
+
+        let (pad, columns) = if narrow { (PAD_NARROW, 1) } else { (PAD, 2) };
+        let content = Column::new()
+            .padding(pad)
+            .spacing(SPACING * 2)
+            .push(grid(fields, columns))
+            .push(grid(choices, columns + 1))
+            .push(precision);
+
+        Scrollable::new(&mut self.scroll)
+            .push(content)
+            .style(theme::Scrollable(*theme))
             .into()
     }
 }
 
-fn c_to_f(s: String) -> String {
-    if let Ok(c) = s.parse::<f64>() {
-        let f = c * ( 9. /  5.) + 32.;
-        format!("{:.1}", f)
-    } else {
-        "err".to_string()
+// A precision as it is named in the selected language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Digits(Precision);
+
+impl std::fmt::Display for Digits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Precision::Decimals(decimals) => write!(f, "{}", tr_with("temperature-decimals", &[("count", &decimals)])),
+            Precision::Significant(figures) => write!(f, "{}", tr_with("temperature-significant", &[("count", &figures)])),
+        }
     }
 }
 
-fn f_to_c(s: String) -> String {
-    if let Ok(f) = s.parse::<f64>() {
-        let c = (f - 32.) * (5. / 9.);
-        format!("{:.1}", c)
-    } else {
-        "err".to_string()
+// the note shown under a field which isn't a temperature, or arithmetic which can't be worked out
+fn problem(scale: Scale, invalid: Invalid) -> String {
+    match invalid {
+        Invalid::NotANumber => tr("temperature-not-a-number"),
+        Invalid::BelowAbsoluteZero => {
+            let limit = locale::number(&temperature::format(scale.from_kelvin(0.), Precision::Decimals(2)));
+            tr_with("temperature-below-absolute-zero", &[("limit", &format!("{} {}", limit, scale.symbol()))])
+        },
+        Invalid::TooHot => tr("temperature-too-hot"),
+        Invalid::OtherScale(other) => {
+            tr_with("temperature-other-scale", &[("symbol", &other.symbol()), ("field", &scale.symbol())])
+        },
+        Invalid::Expression(Problem::NotANumber) => tr("temperature-not-a-number"),
+        Invalid::Expression(Problem::MissingNumber) => tr("temperature-missing-number"),
+        Invalid::Expression(Problem::Unexpected(c)) => tr_with("temperature-unexpected", &[("character", &c)]),
+        Invalid::Expression(Problem::UnclosedBracket) => tr("temperature-unclosed-bracket"),
+        Invalid::Expression(Problem::DivisionByZero) => tr("temperature-division-by-zero"),
+        Invalid::Expression(Problem::TooDeep) => tr("temperature-too-deep"),
     }
-}
\ No newline at end of file
+}
+
+// lays elements out in rows of 'columns', each column as wide as the others
+fn grid(elements: Vec<Element<'_, Message>>, columns: usize) -> Element<'_, Message> {
+    let mut rows = Column::new().spacing(SPACING);
+    let mut elements = elements.into_iter().peekable();
+
+    while elements.peek().is_some() {
+        let row = (0..columns).fold(Row::new().spacing(SPACING * 2), |row, _| match elements.next() {
+            Some(element) => row.push(element),
+            // the last row is filled out so its elements line up with those above
+            None => row.push(Row::new().width(Length::Fill)),
+        });
+        rows = rows.push(row);
+    }
+    rows.into()
+}
@@ -0,0 +1,506 @@
+// # Themes
+// Colours and borders shared by every task, each widget style below is built from a 'Theme'
+// so the whole application changes look when a different theme is selected
+
+use iced::{button, checkbox, container, pick_list, progress_bar, radio, scrollable, slider, text_input,
+           Background, Color, Vector};
+
+use crate::locale::tr;
+
+pub mod file;
+
+// iced's own size for text not given one
+pub const DEFAULT_TEXT_SIZE: u16 = 20;
+
+// ## Theme definitions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub palette: Palette,
+    pub border_radius: f32,
+    pub border_width: f32,
+    // the size of body text, set from the settings rather than the theme
+    pub text_size: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    // the window and the inside of inputs
+    pub background: Color,
+    // panels set apart from the background, such as lists
+    pub surface: Color,
+    pub text: Color,
+    // placeholders and disabled text
+    pub text_muted: Color,
+    pub border: Color,
+    // the colour drawing attention, used for headings, focus and progress
+    pub primary: Color,
+    pub button: Color,
+    pub button_text: Color,
+    pub selection: Color,
+    pub invalid: Color,
+}
+
+impl Theme {
+    pub const LIGHT: Theme = Theme {
+        palette: Palette {
+            background: Color::WHITE,
+            surface: Color::from_rgb(0.95, 0.95, 0.95),
+            text: Color::from_rgb(0.2, 0.2, 0.2),
+            text_muted: Color::from_rgb(0.7, 0.7, 0.7),
+            border: Color::from_rgb(0.7, 0.7, 0.7),
+            primary: Color::from_rgb(0.1, 0.1, 0.6),
+            button: Color::from_rgb(0.87, 0.87, 0.87),
+            button_text: Color::BLACK,
+            selection: Color::from_rgb(0.8, 0.8, 0.8),
+            invalid: Color::from_rgb(0.8, 0.2, 0.2),
+        },
+        border_radius: 5.,
+        border_width: 1.,
+        text_size: DEFAULT_TEXT_SIZE,
+    };
+
+    pub const DARK: Theme = Theme {
+        palette: Palette {
+            background: Color::from_rgb(0.13, 0.14, 0.16),
+            surface: Color::from_rgb(0.18, 0.19, 0.22),
+            text: Color::from_rgb(0.9, 0.9, 0.9),
+            text_muted: Color::from_rgb(0.5, 0.5, 0.5),
+            border: Color::from_rgb(0.35, 0.36, 0.4),
+            primary: Color::from_rgb(0.45, 0.6, 0.95),
+            button: Color::from_rgb(0.26, 0.28, 0.32),
+            button_text: Color::from_rgb(0.95, 0.95, 0.95),
+            selection: Color::from_rgb(0.3, 0.35, 0.5),
+            invalid: Color::from_rgb(0.95, 0.4, 0.4),
+        },
+        border_radius: 5.,
+        border_width: 1.,
+        text_size: DEFAULT_TEXT_SIZE,
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        palette: Palette {
+            background: Color::BLACK,
+            surface: Color::BLACK,
+            text: Color::WHITE,
+            text_muted: Color::from_rgb(0.75, 0.75, 0.75),
+            border: Color::WHITE,
+            primary: Color::from_rgb(1., 1., 0.),
+            button: Color::BLACK,
+            button_text: Color::from_rgb(1., 1., 0.),
+            selection: Color::from_rgb(0., 0.4, 1.),
+            invalid: Color::from_rgb(1., 0.3, 0.3),
+        },
+        border_radius: 0.,
+        border_width: 2.,
+        text_size: DEFAULT_TEXT_SIZE,
+    };
+
+    // a text size chosen for the default text size, grown or shrunk in proportion to the one set
+    pub fn text(&self, size: u16) -> u16 {
+        (u32::from(size) * u32::from(self.text_size) / u32::from(DEFAULT_TEXT_SIZE)) as u16
+    }
+}
+
+// The themes that can be selected from the launcher or the command line,
+// 'Custom' is the user's own theme file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preset {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+    Custom,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::Light,
+        Preset::Dark,
+        Preset::HighContrast,
+        Preset::Custom,
+    ];
+
+    // the theme compiled into the application, None for the custom theme which has to be loaded
+    pub fn builtin(&self) -> Option<Theme> {
+        match self {
+            Preset::Light => Some(Theme::LIGHT),
+            Preset::Dark => Some(Theme::DARK),
+            Preset::HighContrast => Some(Theme::HIGH_CONTRAST),
+            Preset::Custom => None,
+        }
+    }
+
+    // the name used to select the theme from the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Light => "light",
+            Preset::Dark => "dark",
+            Preset::HighContrast => "high-contrast",
+            Preset::Custom => "custom",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Preset> {
+        Preset::ALL.iter().copied().find(|preset| preset.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl std::fmt::Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Preset::Light => tr("theme-light"),
+                Preset::Dark => tr("theme-dark"),
+                Preset::HighContrast => tr("theme-high-contrast"),
+                Preset::Custom => tr("theme-custom"),
+            }
+        )
+    }
+}
+
+
+// ## Widget styles
+
+// The background and default text colour of a whole page
+pub struct Page(pub Theme);
+
+impl container::StyleSheet for Page {
+    fn style(&self) -> container::Style {
+        container::Style {
+            text_color: Some(self.0.palette.text),
+            background: Some(Background::Color(self.0.palette.background)),
+            ..container::Style::default()
+        }
+    }
+}
+
+// A bordered area set apart from the page, such as a list
+pub struct Panel(pub Theme);
+
+impl container::StyleSheet for Panel {
+    fn style(&self) -> container::Style {
+        let Theme { palette, border_radius, border_width, .. } = self.0;
+
+        container::Style {
+            text_color: Some(palette.text),
+            background: Some(Background::Color(palette.surface)),
+            border_radius,
+            border_width,
+            border_color: palette.border,
+        }
+    }
+}
+
+// A small note pointing out a problem with the widget above it
+pub struct Tooltip(pub Theme);
+
+impl container::StyleSheet for Tooltip {
+    fn style(&self) -> container::Style {
+        let Theme { palette, border_radius, border_width, .. } = self.0;
+
+        container::Style {
+            text_color: Some(palette.invalid),
+            background: Some(Background::Color(palette.surface)),
+            border_radius,
+            border_width,
+            border_color: palette.invalid,
+        }
+    }
+}
+
+pub struct Button(pub Theme);
+
+impl button::StyleSheet for Button {
+    fn active(&self) -> button::Style {
+        let Theme { palette, border_radius, border_width, .. } = self.0;
+
+        button::Style {
+            shadow_offset: Vector::default(),
+            background: Some(Background::Color(palette.button)),
+            border_radius,
+            border_width,
+            border_color: palette.border,
+            text_color: palette.button_text,
+        }
+    }
+
+    fn hovered(&self) -> button::Style {
+        button::Style {
+            border_color: self.0.palette.primary,
+            ..self.active()
+        }
+    }
+}
+
+// The button keyboard navigation has moved to
+pub struct FocusedButton(pub Theme);
+
+impl button::StyleSheet for FocusedButton {
+    fn active(&self) -> button::Style {
+        button::Style {
+            background: Some(Background::Color(self.0.palette.selection)),
+            border_color: self.0.palette.primary,
+            ..Button(self.0).active()
+        }
+    }
+}
+
+// A row in a list which can be clicked, the selected row is highlighted
+pub struct ListItem(pub Theme, pub bool);
+
+impl button::StyleSheet for ListItem {
+    fn active(&self) -> button::Style {
+        let palette = self.0.palette;
+
+        button::Style {
+            shadow_offset: Vector::default(),
+            background: match self.1 {
+                true => Some(Background::Color(palette.selection)),
+                false => None,
+            },
+            border_radius: self.0.border_radius,
+            border_width: 0.,
+            border_color: Color::TRANSPARENT,
+            text_color: palette.text,
+        }
+    }
+
+    fn hovered(&self) -> button::Style {
+        button::Style {
+            text_color: self.0.palette.primary,
+            ..self.active()
+        }
+    }
+}
+
+// The states a text input can be shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Enabled,
+    Disabled,
+    Invalid,
+}
+
+pub struct TextInput(pub Theme, pub Input);
+
+impl TextInput {
+    fn style(&self) -> text_input::Style {
+        let Theme { palette, border_radius, border_width, .. } = self.0;
+
+        text_input::Style {
+            background: Background::Color(palette.background),
+            border_radius,
+            border_width,
+            border_color: match self.1 {
+                Input::Invalid => palette.invalid,
+                _ => palette.border,
+            },
+        }
+    }
+}
+
+impl text_input::StyleSheet for TextInput {
+    fn active(&self) -> text_input::Style {
+        self.style()
+    }
+
+    fn focused(&self) -> text_input::Style {
+        match self.1 {
+            Input::Enabled => text_input::Style {
+                border_color: self.0.palette.primary,
+                ..self.style()
+            },
+            _ => self.style(),
+        }
+    }
+
+    fn placeholder_color(&self) -> Color {
+        self.0.palette.text_muted
+    }
+
+    fn value_color(&self) -> Color {
+        match self.1 {
+            Input::Enabled => self.0.palette.text,
+            Input::Disabled => self.0.palette.text_muted,
+            Input::Invalid => self.0.palette.invalid,
+        }
+    }
+
+    fn selection_color(&self) -> Color {
+        match self.1 {
+            Input::Disabled => Color::TRANSPARENT,
+            _ => self.0.palette.selection,
+        }
+    }
+}
+
+pub struct PickList(pub Theme);
+
+impl pick_list::StyleSheet for PickList {
+    fn menu(&self) -> pick_list::Menu {
+        let palette = self.0.palette;
+
+        pick_list::Menu {
+            text_color: palette.text,
+            background: Background::Color(palette.background),
+            border_width: self.0.border_width,
+            border_color: palette.border,
+            selected_text_color: palette.background,
+            selected_background: Background::Color(palette.primary),
+        }
+    }
+
+    fn active(&self) -> pick_list::Style {
+        let Theme { palette, border_radius, border_width, .. } = self.0;
+
+        pick_list::Style {
+            text_color: palette.text,
+            background: Background::Color(palette.background),
+            border_radius,
+            border_width,
+            border_color: palette.border,
+            icon_size: 0.7,
+        }
+    }
+
+    fn hovered(&self) -> pick_list::Style {
+        pick_list::Style {
+            border_color: self.0.palette.primary,
+            ..self.active()
+        }
+    }
+}
+
+pub struct Slider(pub Theme);
+
+impl slider::StyleSheet for Slider {
+    fn active(&self) -> slider::Style {
+        let palette = self.0.palette;
+
+        slider::Style {
+            rail_colors: (palette.border, palette.surface),
+            handle: slider::Handle {
+                shape: slider::HandleShape::Rectangle { width: 8, border_radius: self.0.border_radius },
+                color: palette.button,
+                border_width: self.0.border_width,
+                border_color: palette.border,
+            },
+        }
+    }
+
+    fn hovered(&self) -> slider::Style {
+        let active = self.active();
+
+        slider::Style {
+            handle: slider::Handle {
+                border_color: self.0.palette.primary,
+                ..active.handle
+            },
+            ..active
+        }
+    }
+
+    fn dragging(&self) -> slider::Style {
+        let active = self.active();
+
+        slider::Style {
+            handle: slider::Handle {
+                color: self.0.palette.primary,
+                ..active.handle
+            },
+            ..active
+        }
+    }
+}
+
+pub struct ProgressBar(pub Theme);
+
+impl progress_bar::StyleSheet for ProgressBar {
+    fn style(&self) -> progress_bar::Style {
+        progress_bar::Style {
+            background: Background::Color(self.0.palette.surface),
+            bar: Background::Color(self.0.palette.primary),
+            border_radius: self.0.border_radius,
+        }
+    }
+}
+
+pub struct Radio(pub Theme);
+
+impl radio::StyleSheet for Radio {
+    fn active(&self) -> radio::Style {
+        let palette = self.0.palette;
+
+        radio::Style {
+            background: Background::Color(palette.background),
+            dot_color: palette.primary,
+            border_width: self.0.border_width,
+            border_color: palette.border,
+        }
+    }
+
+    fn hovered(&self) -> radio::Style {
+        radio::Style {
+            border_color: self.0.palette.primary,
+            ..self.active()
+        }
+    }
+}
+
+pub struct Checkbox(pub Theme);
+
+impl checkbox::StyleSheet for Checkbox {
+    fn active(&self, _is_checked: bool) -> checkbox::Style {
+        let palette = self.0.palette;
+
+        checkbox::Style {
+            background: Background::Color(palette.background),
+            checkmark_color: palette.primary,
+            border_radius: self.0.border_radius,
+            border_width: self.0.border_width,
+            border_color: palette.border,
+        }
+    }
+
+    fn hovered(&self, is_checked: bool) -> checkbox::Style {
+        checkbox::Style {
+            border_color: self.0.palette.primary,
+            ..self.active(is_checked)
+        }
+    }
+}
+
+pub struct Scrollable(pub Theme);
+
+impl scrollable::StyleSheet for Scrollable {
+    fn active(&self) -> scrollable::Scrollbar {
+        let palette = self.0.palette;
+
+        scrollable::Scrollbar {
+            background: None,
+            border_radius: self.0.border_radius,
+            border_width: 0.,
+            border_color: Color::TRANSPARENT,
+            scroller: scrollable::Scroller {
+                color: palette.border,
+                border_radius: self.0.border_radius,
+                border_width: 0.,
+                border_color: Color::TRANSPARENT,
+            },
+        }
+    }
+
+    fn hovered(&self) -> scrollable::Scrollbar {
+        let active = self.active();
+
+        scrollable::Scrollbar {
+            scroller: scrollable::Scroller {
+                color: self.0.palette.primary,
+                ..active.scroller
+            },
+            ..active
+        }
+    }
+}
@@ -0,0 +1,163 @@
+// # Theme files
+// A user defined theme read from 'theme.toml' in the config directory. Every value is optional,
+// anything left out is taken from the built in theme named by 'extends':
+//
+//     extends = "dark"
+//     border_radius = 3.0
+//     border_width = 1.0
+//
+//     [palette]
+//     primary = "#ff8800"
+//     invalid = "#ff0000aa"
+//
+// The file is polled while the custom theme is selected so changes show up without a restart
+
+use iced::Color;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::{Palette, Preset, Theme};
+use crate::config;
+
+const FILE_NAME: &str = "theme.toml";
+// border widths beyond this swallow the widgets they surround
+const MAX_BORDER_WIDTH: f32 = 10.;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    extends: Option<String>,
+    border_radius: Option<f32>,
+    border_width: Option<f32>,
+    #[serde(default)]
+    palette: PaletteFile,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct PaletteFile {
+    background: Option<String>,
+    surface: Option<String>,
+    text: Option<String>,
+    text_muted: Option<String>,
+    border: Option<String>,
+    primary: Option<String>,
+    button: Option<String>,
+    button_text: Option<String>,
+    selection: Option<String>,
+    invalid: Option<String>,
+}
+
+// Reads and validates a theme file, every problem found is listed in the error
+pub fn load(path: &Path) -> Result<Theme, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("{} could not be read: {}", path.display(), e))?;
+
+    parse(&text).map_err(|e| format!("{} is not a valid theme: {}", path.display(), e))
+}
+
+fn parse(text: &str) -> Result<Theme, String> {
+    let file: ThemeFile = toml::from_str(text).map_err(|e| e.to_string())?;
+    let mut errors = Vec::new();
+
+    let base = match &file.extends {
+        None => Theme::LIGHT,
+        Some(name) => match Preset::from_name(name).and_then(|preset| preset.builtin()) {
+            Some(theme) => theme,
+            None => {
+                errors.push(format!("extends: unknown theme '{}', expected light, dark or high-contrast", name));
+                Theme::LIGHT
+            },
+        },
+    };
+
+    let mut colour = |key: &str, value: &Option<String>, default: Color| match value {
+        None => default,
+        Some(value) => parse_colour(value).unwrap_or_else(|| {
+            errors.push(format!("palette.{}: expected a colour written as \"#rrggbb\" or \"#rrggbbaa\", found \"{}\"", key, value));
+            default
+        }),
+    };
+
+    let p = &file.palette;
+    let palette = Palette {
+        background: colour("background", &p.background, base.palette.background),
+        surface: colour("surface", &p.surface, base.palette.surface),
+        text: colour("text", &p.text, base.palette.text),
+        text_muted: colour("text_muted", &p.text_muted, base.palette.text_muted),
+        border: colour("border", &p.border, base.palette.border),
+        primary: colour("primary", &p.primary, base.palette.primary),
+        button: colour("button", &p.button, base.palette.button),
+        button_text: colour("button_text", &p.button_text, base.palette.button_text),
+        selection: colour("selection", &p.selection, base.palette.selection),
+        invalid: colour("invalid", &p.invalid, base.palette.invalid),
+    };
+
+    let border_radius = file.border_radius.unwrap_or(base.border_radius);
+    if !border_radius.is_finite() || border_radius < 0. {
+        errors.push(format!("border_radius: must be zero or more, found {}", border_radius));
+    }
+
+    let border_width = file.border_width.unwrap_or(base.border_width);
+    if !(0. ..=MAX_BORDER_WIDTH).contains(&border_width) {
+        errors.push(format!("border_width: must be between 0 and {}, found {}", MAX_BORDER_WIDTH, border_width));
+    }
+
+    if errors.is_empty() {
+        Ok(Theme { palette, border_radius, border_width, ..base })
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+// "#rrggbb" or "#rrggbbaa"
+fn parse_colour(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+
+    Some(Color::from_rgba8(channel(0)?, channel(2)?, channel(4)?, f32::from(alpha) / 255.))
+}
+
+
+// Remembers when the theme file was last read so it is only reloaded after it changes
+#[derive(Debug, Default)]
+pub struct Watcher {
+    modified: Option<SystemTime>,
+    // whether the last attempt found a file at all
+    seen: bool,
+}
+
+impl Watcher {
+    pub fn path() -> Option<PathBuf> {
+        config::path(FILE_NAME)
+    }
+
+    // Loads the file regardless of whether it changed
+    pub fn load(&mut self) -> Result<Theme, String> {
+        let path = Watcher::path().ok_or_else(|| "no config directory could be found for the theme file".to_string())?;
+        let metadata = std::fs::metadata(&path);
+
+        self.seen = metadata.is_ok();
+        self.modified = metadata.and_then(|m| m.modified()).ok();
+        load(&path)
+    }
+
+    // Reloads the file if it has been created, edited or removed since it was last read
+    pub fn poll(&mut self) -> Option<Result<Theme, String>> {
+        let path = Watcher::path()?;
+        let metadata = std::fs::metadata(&path);
+        let modified = metadata.as_ref().ok().and_then(|m| m.modified().ok());
+
+        if metadata.is_ok() == self.seen && modified == self.modified {
+            None
+        } else {
+            Some(self.load())
+        }
+    }
+}
@@ -1,84 +1,99 @@
-use iced::{button, executor, Align, Button, Row, Element, Settings, Text, HorizontalAlignment,
-           Length, Column, ProgressBar, Slider, slider, time, Application, Command, Subscription};
+use iced::{button, Align, Button, Row, Element, Text, HorizontalAlignment,
+           Length, Column, ProgressBar, Size, Slider, slider, time, window, Subscription};
 use std::time::{Duration, Instant};
+use sevenguis_model::timer::{self, DURATION_RANGE};
+use crate::locale::tr;
+use crate::task::{GuiTask, Preferences, Status};
+use crate::theme::{self, Theme};
 
 // the window size is an unsigned 32 bit integer, the padding is unsigned 16 bit integer
-const SIZE: (u32, u32) = (350, 180);
+const DEFAULT_SIZE: (u32, u32) = (350, 240);
+const MIN_SIZE: (u32, u32) = (200, 240);
 const PAD: u16 = 25;
+// below this width the labels go above the bars rather than beside them
+const NARROW: f32 = 300.;
+const PAD_NARROW: u16 = 10;
+const LABEL_SPACING: u16 = 5;
 
-pub fn main() -> iced::Result{
-    // Set the window properties
-    let mut settings = Settings::default();
-    settings.window.size = SIZE;
-    settings.window.resizable = false;
 
-    // run the application
-    Timer::run(settings)
-}
-
-
-struct Timer {
-    elapsed: f32,
-    max_time: f32,
+pub struct Timer {
+    model: timer::Timer,
     btn: button::State,
     slider: slider::State,
-    start_time: Instant,
+    // the model counts in seconds between ticks, so the time of the last one is kept here
+    last_tic: Instant,
 }
 
 impl Default for Timer {
     fn default() -> Self {
         Timer {
-            elapsed: 0.,
-            max_time: 30.,
+            model: timer::Timer::default(),
             btn: button::State::default(),
             slider: slider::State::default(),
-            start_time: Instant::now(),
+            last_tic: Instant::now(),
         }
     }
 }
 
 // define the possible interactions of the application
 #[derive(Debug, Clone, Copy)]
-enum Message {
+pub enum Message {
     ResetPressed,
     SliderChange(f32),
     Tic(Instant),
 }
 
-// Can't use the simplified 'Sandbox' for asyncronous applications
-impl Application for Timer {
-    type Executor = executor::Default;
+// The timer needs a subscription, so the launcher has to be an 'Application' rather than a 'Sandbox'
+impl GuiTask for Timer {
     type Message = Message;
-    type Flags = ();
+    type Model = timer::Timer;
 
-    // in an Application flags can be provided to the creation of the model
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        (
-            Self::default(),
-            Command::none()
-            )
+    const STATUS: Status = Status::Implemented;
+    const THUMBNAIL: &'static [u8] = include_bytes!("../images/Timer.PNG");
+
+    fn window() -> window::Settings {
+        window::Settings {
+            size: DEFAULT_SIZE,
+            min_size: Some(MIN_SIZE),
+            ..window::Settings::default()
+        }
     }
 
-    fn title(&self) -> String {
-        String::from("Timer")
+    fn new() -> Self {
+        Self::default()
     }
 
-    fn update(&mut self, message:Message) -> Command<Message> {
-        match message {
-            Message::ResetPressed => {
-                self.start_time = Instant::now();
-                self.elapsed = 0.;
-            }
-            Message::SliderChange(v) => (self.max_time = v),
-            Message::Tic(_) => {
-                let now = Instant::now();
-                let elapsed = (now - self.start_time).as_secs_f32();
-                if elapsed <= self.max_time {
-                    self.elapsed = elapsed
-                }
-            },
-        };
-        Command::none()
+    // ticks are turned into the seconds since the last one, so a recording replays at any speed
+    fn model_message(&self, message: &Message) -> Option<timer::Message> {
+        Some(match *message {
+            Message::ResetPressed => timer::Message::ResetPressed,
+            Message::SliderChange(v) => timer::Message::SliderChange(v),
+            Message::Tic(now) => timer::Message::Tic((now - self.last_tic).as_secs_f32()),
+        })
+    }
+
+    fn model(&self) -> &Self::Model {
+        &self.model
+    }
+
+    fn model_mut(&mut self) -> &mut Self::Model {
+        &mut self.model
+    }
+
+    // ticks are dropped while the debugger has the timer paused, they must not be caught up on
+    fn rewind(&mut self, model: Self::Model) {
+        self.model = model;
+        self.last_tic = Instant::now();
+    }
+
+    fn update(&mut self, message:Message) {
+        let model_message = self.model_message(&message);
+        if let Message::Tic(now) = message {
+            self.last_tic = now;
+        }
+        if let Some(model_message) = model_message {
+            self.model.update(model_message);
+        }
     }
 
     // produces messages for update to handle as long as it is running
@@ -87,47 +102,69 @@ impl Application for Timer {
         time::every(Duration::from_millis(16)).map(Message::Tic)
     }
 
-    fn view(&mut self) -> Element<Message> {
-
-        let progbar = ProgressBar::new(0.0..=self.max_time, self.elapsed);
+    fn restore(&self, preferences: &Preferences) -> Vec<Message> {
+        match preferences.get("duration").and_then(|v| v.as_float()) {
+            Some(duration) => {
+                let duration = (duration as f32).max(*DURATION_RANGE.start()).min(*DURATION_RANGE.end());
+                vec![Message::SliderChange(duration)]
+            },
+            None => Vec::new(),
+        }
+    }
 
-        let slider_time = Slider::new(&mut self.slider,
-                                      1.0..=60.,
-                                      self.max_time,
-                                      Message::SliderChange
-        ).step(0.1);
+    fn preferences(&self) -> Preferences {
+        let mut preferences = Preferences::new();
+        preferences.insert("duration".to_string(), f64::from(self.model.max_time()).into());
+        preferences
+    }
 
-        let top_row = Row::new()
-            .push(
-                Text::new("Elapsed Time: ")
-            )
-            .push(progbar);
+    fn view(&mut self, theme: &Theme, size: Size) -> Element<'_, Message> {
+        let narrow = size.width < NARROW;
 
-        let bot_row = Row::new()
-            .push(
-                Text::new("Duration: ")
-            )
-            .push(slider_time);
+        let progbar = ProgressBar::new(0.0..=self.model.max_time(), self.model.elapsed())
+            .style(theme::ProgressBar(*theme));
 
+        let slider_time = Slider::new(&mut self.slider,
+                                      DURATION_RANGE,
+                                      self.model.max_time(),
+                                      Message::SliderChange
+        ).step(0.1)
+            .style(theme::Slider(*theme));
+
+        let label = |id| Text::new(tr(id)).size(theme.text_size);
+
+        let (top_row, bot_row): (Element<'_, Message>, Element<'_, Message>) = match narrow {
+            true => (
+                Column::new().push(label("timer-elapsed")).push(progbar).into(),
+                Column::new().push(label("timer-duration")).push(slider_time).into(),
+            ),
+            false => (
+                Row::new().spacing(LABEL_SPACING).push(label("timer-elapsed")).push(progbar).into(),
+                Row::new().spacing(LABEL_SPACING).push(label("timer-duration")).push(slider_time).into(),
+            ),
+        };
 
         Column::new()
-            .padding(PAD)
+            .padding(if narrow { PAD_NARROW } else { PAD })
             .spacing(10)
             .align_items(Align::Center)
             .push(top_row)
             .push(
-                Text::new(format!("{:.1}s", &self.elapsed))
+                Text::new(format!("{:.1}s", self.model.elapsed()))
+                    .size(theme.text_size)
                     .horizontal_alignment(HorizontalAlignment::Left)
                     .width(Length::FillPortion(2)),
             )
             .push(bot_row)
             .push(
                 Button::new(&mut self.btn,
-                            Text::new("Reset")
+                            Text::new(tr("timer-reset"))
+                    .size(theme.text_size)
                     .horizontal_alignment(HorizontalAlignment::Center)
                     .width(Length::Fill)
                 )
                     .width(Length::Fill)
+                    .style(theme::Button(*theme))
                     .on_press(Message::ResetPressed),
             )
             .into()
@@ -0,0 +1,308 @@
+// # Workspace
+// Several tasks side by side in a grid of panes which can be split, resized, swapped by dragging
+// their title bars and closed. Each pane has a task of its own, independent of the launcher's
+// and of other panes showing the same task. Messages for the panes' tasks are handed back to the
+// launcher, which records them before they are handled. Keyboard shortcuts go to the task in the
+// pane last clicked, not to every pane at once
+
+use iced::{button, pane_grid, pick_list, Align, Button, Container, Element, Length, PaneGrid, PickList, Row,
+           Size, Space, Subscription, Text};
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{tr, Language};
+use crate::settings;
+use crate::task::{self, DynTask, TaskMessage, REGISTRY};
+use crate::theme::{self, Theme};
+
+const PAD: u16 = 5;
+const TEXT_SIZE: u16 = 14;
+// how far from a split the pointer can be to start resizing it
+const RESIZE_LEEWAY: u16 = 10;
+// the height of a pane's title bar and its controls beyond the height of their text
+const TITLE_BAR_PAD: f32 = 20.;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Open(pane_grid::Pane, usize),
+    Split(pane_grid::Pane, pane_grid::Axis),
+    Close(pane_grid::Pane),
+    Resized(pane_grid::ResizeEvent),
+    Dragged(pane_grid::DragEvent),
+    Task(pane_grid::Pane, TaskMessage),
+    Focus(pane_grid::Pane),
+}
+
+pub struct Workspace {
+    panes: pane_grid::State<Pane>,
+    // how many tasks have been created in the panes, numbering each copy of a task for the recording
+    created: usize,
+    // the pane whose task has the keyboard
+    focused: Option<pane_grid::Pane>,
+}
+
+impl Workspace {
+    // rebuilds the layout saved by an earlier run, its tasks are only created once it is shown
+    pub fn new(layout: Option<&Layout>) -> Self {
+        let panes = match layout {
+            Some(layout) => pane_grid::State::with_configuration(layout.configuration()),
+            None => pane_grid::State::new(Pane::default()).0,
+        };
+
+        let focused = panes.iter().next().map(|(pane, _)| *pane);
+        Workspace { panes, created: 0, focused }
+    }
+
+    // creates the tasks of the saved layout which haven't been yet, so a workspace which is never
+    // opened leaves the launcher's tasks alone. Returns the messages restoring their saved preferences
+    pub fn show(&mut self, saved: &settings::Settings) -> Vec<(pane_grid::Pane, TaskMessage)> {
+        let mut shown = Vec::new();
+        for (pane, state) in self.panes.iter_mut() {
+            if let Some(index) = state.pending.take() {
+                self.created += 1;
+                state.task = Some((index, (REGISTRY[index].create)()));
+                state.instance = self.created;
+                shown.push(*pane);
+            }
+        }
+        shown.into_iter().flat_map(|pane| self.restore(pane, saved)).collect()
+    }
+
+    // the messages for the panes' tasks are returned rather than handled, for the launcher to dispatch
+    pub fn update(&mut self, message: Message, saved: &settings::Settings) -> Vec<(pane_grid::Pane, TaskMessage)> {
+        match message {
+            Message::Open(pane, index) => {
+                if let Some(state) = self.panes.get_mut(&pane) {
+                    self.created += 1;
+                    // the old task goes first, so whatever it holds on to is free for the new one
+                    state.task = None;
+                    state.pending = None;
+                    state.task = Some((index, (REGISTRY[index].create)()));
+                    state.instance = self.created;
+                }
+                self.focused = Some(pane);
+                return self.restore(pane, saved);
+            },
+            Message::Split(pane, axis) => {
+                self.panes.split(axis, &pane, Pane::default());
+            },
+            Message::Close(pane) => {
+                // the last pane stays so there is somewhere to open a task
+                if self.panes.len() > 1 {
+                    // the keyboard goes to the pane taking the closed one's place
+                    if let Some((_, sibling)) = self.panes.close(&pane) {
+                        if self.focused == Some(pane) {
+                            self.focused = Some(sibling);
+                        }
+                    }
+                }
+            },
+            Message::Resized(pane_grid::ResizeEvent { split, ratio }) => self.panes.resize(&split, ratio),
+            Message::Dragged(pane_grid::DragEvent::Dropped { pane, target }) => self.panes.swap(&pane, &target),
+            Message::Dragged(_) => (),
+            Message::Task(pane, message) => return vec![(pane, message)],
+            Message::Focus(pane) => self.focused = Some(pane),
+        }
+        Vec::new()
+    }
+
+    // a pane's task with its index in the registry and which copy of a task it is, None for an empty pane
+    pub fn task_mut(&mut self, pane: &pane_grid::Pane) -> Option<(usize, usize, &mut dyn DynTask)> {
+        let state = self.panes.get_mut(pane)?;
+        let (index, task) = state.task.as_mut()?;
+        Some((*index, state.instance, task.as_mut()))
+    }
+
+    pub fn language_changed(&self, previous: Language, language: Language) -> Vec<(pane_grid::Pane, TaskMessage)> {
+        self.panes.iter()
+            .filter_map(|(pane, state)| Some((*pane, state.task.as_ref()?)))
+            .flat_map(|(pane, (_, task))| task.language_changed(previous, language).into_iter().map(move |m| (pane, m)))
+            .collect()
+    }
+
+    fn restore(&self, pane: pane_grid::Pane, saved: &settings::Settings) -> Vec<(pane_grid::Pane, TaskMessage)> {
+        let (index, task) = match self.panes.get(&pane).and_then(|state| state.task.as_ref()) {
+            Some(task) => task,
+            None => return Vec::new(),
+        };
+        let preferences = saved.tasks.get(REGISTRY[*index].name).cloned().unwrap_or_default();
+        task.restore(&preferences).into_iter().map(|message| (pane, message)).collect()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch(self.panes.iter().filter_map(|(pane, state)| {
+            let (_, task) = state.task.as_ref()?;
+            let subscription = match self.focused == Some(*pane) {
+                true => Subscription::batch(vec![task.subscription(), task.shortcuts()]),
+                false => task.subscription(),
+            };
+            Some(subscription.with(*pane).map(|(pane, m)| Message::Task(pane, m)))
+        }))
+    }
+
+    // 'size' is the space for the whole grid, each task is given the part its pane takes
+    pub fn view(&mut self, theme: Theme, size: Size) -> Element<'_, Message> {
+        let closable = self.panes.len() > 1;
+        let regions = self.panes.layout().pane_regions(f32::from(PAD), size);
+        let title_bar = f32::from(theme.text(TEXT_SIZE)) + TITLE_BAR_PAD;
+
+        PaneGrid::new(&mut self.panes, |pane, state| {
+            let size = regions.get(&pane).map_or(size, |region| {
+                Size::new(region.width, (region.height - title_bar).max(0.))
+            });
+            state.view(pane, closable, size, theme)
+        })
+            .spacing(PAD)
+            .on_click(Message::Focus)
+            .on_drag(Message::Dragged)
+            .on_resize(RESIZE_LEEWAY, Message::Resized)
+            .into()
+    }
+
+    // the arrangement of the panes and the task in each, to be saved in the settings
+    pub fn layout(&self) -> Layout {
+        Layout::of(self.panes.layout(), &self.panes)
+    }
+}
+
+// ## A single pane
+#[derive(Default)]
+struct Pane {
+    // the task's index in the registry, None until one has been picked
+    task: Option<(usize, Box<dyn DynTask>)>,
+    // the task saved in the layout, until the workspace is shown and it is created
+    pending: Option<usize>,
+    // numbers the task among those created in the workspace, a task picked again is a new copy
+    instance: usize,
+    picker: pick_list::State<Choice>,
+    split_right: button::State,
+    split_down: button::State,
+    close: button::State,
+}
+
+impl Pane {
+    fn view(&mut self, pane: pane_grid::Pane, closable: bool, size: Size, theme: Theme)
+        -> pane_grid::Content<'_, Message> {
+        let selected = self.task.as_ref().map(|(index, _)| Choice(*index));
+        let title = self.task.as_ref().map_or_else(|| tr("workspace-empty"), |(index, _)| REGISTRY[*index].title());
+        let choices: Vec<Choice> = (0..REGISTRY.len()).map(Choice).collect();
+        let text_size = theme.text(TEXT_SIZE);
+
+        let picker = PickList::new(&mut self.picker, choices, selected, move |choice| Message::Open(pane, choice.0))
+            .text_size(text_size)
+            .style(theme::PickList(theme));
+
+        let button = |state, id| Button::new(state, Text::new(tr(id)).size(text_size))
+            .padding(3)
+            .style(theme::Button(theme));
+
+        let mut close = button(&mut self.close, "workspace-close");
+        if closable {
+            close = close.on_press(Message::Close(pane));
+        }
+
+        let controls = Row::new()
+            .spacing(PAD)
+            .align_items(Align::Center)
+            .push(picker)
+            .push(button(&mut self.split_right, "workspace-split-right")
+                .on_press(Message::Split(pane, pane_grid::Axis::Vertical)))
+            .push(button(&mut self.split_down, "workspace-split-down")
+                .on_press(Message::Split(pane, pane_grid::Axis::Horizontal)))
+            .push(close);
+
+        let body: Element<'_, Message> = match &mut self.task {
+            Some((_, task)) => task.view(&theme, size).map(move |m| Message::Task(pane, m)),
+            None => Container::new(Text::new(tr("workspace-pick")).size(theme.text_size).color(theme.palette.text_muted))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .into(),
+        };
+
+        pane_grid::Content::new(Container::new(body).width(Length::Fill).height(Length::Fill).style(theme::Page(theme)))
+            .title_bar(pane_grid::TitleBar::new(title)
+                .title_size(text_size)
+                .padding(PAD)
+                .controls(Row::new().push(Space::with_width(Length::Units(PAD))).push(controls))
+                .always_show_controls()
+                .style(theme::Panel(theme)))
+            .style(theme::Panel(theme))
+    }
+}
+
+// A task in the pane's picker, by its index in the registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Choice(usize);
+
+impl std::fmt::Display for Choice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", REGISTRY[self.0].title())
+    }
+}
+
+
+// ## Saving the layout
+// Tasks are saved by name, a task which no longer exists leaves its pane empty.
+// TOML has no enums, so each node is a table saying which kind it is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Layout {
+    Split {
+        axis: Axis,
+        ratio: f32,
+        a: Box<Layout>,
+        b: Box<Layout>,
+    },
+    Pane {
+        task: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Axis {
+    // panes above each other
+    Horizontal,
+    // panes beside each other
+    Vertical,
+}
+
+impl Layout {
+    fn of(node: &pane_grid::Node, panes: &pane_grid::State<Pane>) -> Layout {
+        match node {
+            pane_grid::Node::Split { axis, ratio, a, b, .. } => Layout::Split {
+                axis: match axis {
+                    pane_grid::Axis::Horizontal => Axis::Horizontal,
+                    pane_grid::Axis::Vertical => Axis::Vertical,
+                },
+                ratio: *ratio,
+                a: Box::new(Layout::of(a, panes)),
+                b: Box::new(Layout::of(b, panes)),
+            },
+            pane_grid::Node::Pane(pane) => Layout::Pane {
+                task: panes.get(pane)
+                    .and_then(|state| state.task.as_ref().map(|(index, _)| *index).or(state.pending))
+                    .map(|index| REGISTRY[index].name.to_string()),
+            },
+        }
+    }
+
+    fn configuration(&self) -> pane_grid::Configuration<Pane> {
+        match self {
+            Layout::Split { axis, ratio, a, b } => pane_grid::Configuration::Split {
+                axis: match axis {
+                    Axis::Horizontal => pane_grid::Axis::Horizontal,
+                    Axis::Vertical => pane_grid::Axis::Vertical,
+                },
+                ratio: ratio.clamp(0., 1.),
+                a: Box::new(a.configuration()),
+                b: Box::new(b.configuration()),
+            },
+            Layout::Pane { task } => pane_grid::Configuration::Pane(Pane {
+                pending: task.as_deref().and_then(task::index),
+                ..Pane::default()
+            }),
+        }
+    }
+}